@@ -12,6 +12,8 @@ use once_cell::sync::Lazy;
 pub static OUTPUT_FILE_NAME_EXTENSION_AS_PATH_BUF: Lazy<PathBuf> = Lazy::new(|| PathBuf::from(OUTPUT_FILE_NAME_EXTENSION_AS_STR) );
 pub const OUTPUT_FILE_NAME_EXTENSION_AS_STR: &str = "html";
 pub const FILE_NAME_IS_NONE_AS_STR: &str = "?";
+pub const DATA_DIR_AS_STR: &str = "data";
+pub const ENV_PREFIX_AS_STR: &str = "SITA_";
 
 #[derive(Debug)]
 pub struct Args {
@@ -39,6 +41,75 @@ pub struct Args {
     /// TODO: add support for OsStr.
     pub(crate) extra_list: Option<List<PathBuf>>,
 
+    /// Data directory path buffer, for the `data` template variable.
+    /// Example directory: "data/"
+    /// Default: "data" if it exists.
+    pub(crate) data_dir: Option<PathBuf>,
+
+    /// Environment variable prefix, for the `env` template variable.
+    /// Example: "SITA_" means `SITA_ANALYTICS_ID` becomes `env.analytics_id`.
+    /// Default: "SITA_".
+    pub(crate) env_prefix: Option<String>,
+
+    /// Environment variable allowed names, without the prefix.
+    /// Only these names are exposed, so secrets are not leaked by accident.
+    /// Example: ["analytics_id", "stage"]
+    pub(crate) env_list: Option<List<String>>,
+
+    /// Archive flag: true means generate archive pages of dated pages,
+    /// i.e. "/archive/", "/archive/2026/", "/archive/2026/10/", etc.
+    pub(crate) archive: bool,
+
+    /// Archive template name, for the archive index page.
+    /// Example: "archive.html"
+    /// Default: the page template.
+    pub(crate) archive_template: Option<String>,
+
+    /// Archive year template name, for each page that lists a year's pages.
+    /// Example: "archive-year.html"
+    /// Default: the page template.
+    pub(crate) archive_year_template: Option<String>,
+
+    /// Archive month template name, for each page that lists a month's pages.
+    /// Example: "archive-month.html"
+    /// Default: the page template.
+    pub(crate) archive_month_template: Option<String>,
+
+    /// Base URL, such as "https://example.com", for absolute links,
+    /// such as in feeds, because a feed reader needs absolute links.
+    /// Default: none, which keeps root-relative links.
+    pub(crate) base_url: Option<String>,
+
+    /// Description length, i.e. the most characters in the `description` variable.
+    /// Example: 160 means truncate a longer description on a word boundary.
+    /// Default: 160.
+    pub(crate) description_length: Option<usize>,
+
+    /// Drafts flag: true means build pages with front matter `draft: true`.
+    pub(crate) drafts: bool,
+
+    /// Feeds flag: true means write feeds, i.e. Atom "feed.xml",
+    /// RSS "rss.xml", and JSON Feed "feed.json", for the site and each section.
+    pub(crate) feeds: bool,
+
+    /// Feed length, i.e. the most entries per feed.
+    /// Default: 20.
+    pub(crate) feed_length: Option<usize>,
+
+    /// Feed full content flag: true means each feed entry has its content,
+    /// rather than only its summary.
+    pub(crate) feed_full_content: bool,
+
+    /// Future flag: true means build pages with a front matter `publish_date`,
+    /// or else `date`, that is after now.
+    pub(crate) future: bool,
+
+    /// Language list, i.e. the site language codes, and the first is the default.
+    /// Example: ["en", "fr"] means "about.fr.md" is the French variant of "about.md",
+    /// and outputs to "fr/about.html".
+    /// Default: no languages, which means no language variants.
+    pub(crate) language_list: Option<List<String>>,
+
     /// Log level: 0=none, 1=error, 2=warn, 3=info, 4=debug, 5=trace.
     /// Example: 5 means print debug diagnostics.
     pub(crate) log_level: Option<::log::Level>,
@@ -48,12 +119,100 @@ pub struct Args {
     /// TODO: add support for OsStr.
     pub(crate) output_file_name_extension: Option<PathBuf>,
 
-    /// Settings map for the program.
-    /// Example: {"alfa" => "bravo", "charlie" => "delta"}
+    /// Table of contents depth, i.e. the deepest heading level to include.
+    /// Example: 3 means include h1, h2, h3.
+    /// Default: 3.
+    pub(crate) toc_depth: Option<u8>,
+
+    /// Settings map for the program, by dotted key path and string value.
+    /// Example: {"alfa" => "bravo", "site.author.name" => "Ada"}
     /// TODO: add support for OsStr.
-    #[allow(dead_code)] //TODO live
     pub(crate) settings: Option<Map<String, String>>,
 
+    /// Settings map for the program, by dotted key path and JSON value text.
+    /// Example: {"count" => "3", "site.tags" => "[\"alfa\", \"bravo\"]"}
+    pub(crate) settings_json: Option<Map<String, String>>,
+
+    /// Not found template name, for the page "404.html" at the output root,
+    /// which is "404.md" if any, or else a generated page.
+    /// Example: "404.html"
+    /// Default: none, which renders "404.md", if any, via the page template.
+    pub(crate) not_found_template: Option<String>,
+
+    /// Paginate by number, i.e. the most items per chunk of a listing page,
+    /// such as a section index page; its front matter `paginate_by` wins.
+    /// Example: 10 means "/posts/" lists 1-10, "/posts/page/2/" lists 11-20, etc.
+    /// Default: no pagination.
+    pub(crate) paginate_by: Option<usize>,
+
+    /// Permalink map, i.e. a URL pattern per section directory, for its pages
+    /// and its subsections' pages; a page's front matter `url` wins.
+    /// Example: {"posts" => "/blog/:year/:month/:slug/"}
+    /// Default: each URL is from its input path.
+    pub(crate) permalink_map: Option<Map<String, String>>,
+
+    /// Redirect map, i.e. site-wide redirects from an old URL to a new URL,
+    /// in addition to each page's front matter `aliases`.
+    /// Example: {"/blog/" => "/posts/"}
+    pub(crate) redirect_map: Option<Map<String, String>>,
+
+    /// Redirects file flag: true means write "_redirects", with every redirect,
+    /// for hosts such as Netlify and Cloudflare Pages.
+    pub(crate) redirects_file: bool,
+
+    /// Robots disallow list, i.e. the URL path prefixes that robots.txt disallows.
+    /// Example: "/drafts/" means disallow "/drafts/" for all user agents.
+    pub(crate) robots_disallow_list: Option<List<String>>,
+
+    /// Robots flag: true means write "robots.txt", with any disallow rules,
+    /// and with a link to the sitemap, if any.
+    pub(crate) robots: bool,
+
+    /// Related count, i.e. the most pages in each page's variable `related`,
+    /// which are the pages that share the most taxonomy terms.
+    /// Example: 3 means up to 3 related pages.
+    /// Default: 5.
+    pub(crate) related_count: Option<usize>,
+
+    /// Remove title flag that sets whether to remove the first H1 from `content`.
+    /// Example: true means a template can show `title` without showing it twice.
+    pub(crate) remove_title: bool,
+
+    /// Search flag: true means write "search-index.json", with each listed page's fields,
+    /// such as for client-side search.
+    pub(crate) search: bool,
+
+    /// Search field list, i.e. the fields of each page in the search index.
+    /// Example: ["url", "title", "description"]
+    /// Default: ["url", "title", "headings", "text"].
+    pub(crate) search_field_list: Option<List<String>>,
+
+    /// Search inverted index flag: true means the search index also has `index`,
+    /// which maps each word to its pages, so browser-side search is small.
+    pub(crate) search_inverted_index: bool,
+
+    /// Search section list, i.e. the section directories whose pages are in the search index.
+    /// Example: ["docs"] means the pages in "docs" and its subsections.
+    /// Default: every listed page.
+    pub(crate) search_section_list: Option<List<String>>,
+
+    /// Sitemap flag: true means write "sitemap.xml", with every rendered page.
+    pub(crate) sitemap: bool,
+
+    /// Taxonomy list of names, which are also front matter keys.
+    /// Example: ["tags", "categories"] means generate "/tags/rust/" etc.
+    pub(crate) taxonomy_list: Option<List<String>>,
+
+    /// Taxonomy template name, for each page that lists a taxonomy's terms.
+    /// Example: "taxonomy.html"
+    /// Default: the page template.
+    pub(crate) taxonomy_template: Option<String>,
+
+    /// Term template name, for each page that lists a term's pages.
+    /// Example: "term.html"
+    /// Default: the page template.
+    pub(crate) term_template: Option<String>,
+
     /// Test flag that sets whether the app prints diagnostics.
     /// Example: true means print diagnostics.
     pub(crate) test: bool,
@@ -65,9 +224,43 @@ impl std::default::Default for Args {
         output_list: None,
         template_list: None,
         extra_list: None,
+        data_dir: None,
+        env_prefix: None,
+        env_list: None,
+        archive: false,
+        archive_template: None,
+        archive_year_template: None,
+        archive_month_template: None,
+        base_url: None,
+        description_length: None,
+        drafts: false,
+        feeds: false,
+        feed_length: None,
+        feed_full_content: false,
+        future: false,
+        language_list: None,
         log_level: None,
         output_file_name_extension: None,
+        toc_depth: None,
         settings: None,
+        settings_json: None,
+        not_found_template: None,
+        paginate_by: None,
+        permalink_map: None,
+        redirect_map: None,
+        redirects_file: false,
+        robots_disallow_list: None,
+        robots: false,
+        related_count: None,
+        remove_title: false,
+        search: false,
+        search_field_list: None,
+        search_inverted_index: false,
+        search_section_list: None,
+        sitemap: false,
+        taxonomy_list: None,
+        taxonomy_template: None,
+        term_template: None,
         test: false,
     }}
 }
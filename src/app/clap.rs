@@ -69,8 +69,181 @@ pub fn app() -> Command {
         .value_parser(clap::value_parser!(PathBuf))
         .action(clap::ArgAction::Append)
     )
+    .arg(Arg::new("archive")
+        .help("Generate archive pages of dated pages, by year and by month, such as \"/archive/2026/10/\".\nExample: --archive")
+        .long("archive")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("archive_template")
+        .help("The template name for the archive index page.\nDefault: the page template.\nExample: --archive-template archive.html")
+        .long("archive-template")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+    )
+    .arg(Arg::new("archive_year_template")
+        .help("The template name for each archive page that lists a year's pages.\nDefault: the page template.\nExample: --archive-year-template archive-year.html")
+        .long("archive-year-template")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+    )
+    .arg(Arg::new("archive_month_template")
+        .help("The template name for each archive page that lists a month's pages.\nDefault: the page template.\nExample: --archive-month-template archive-month.html")
+        .long("archive-month-template")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+    )
+    .arg(Arg::new("base_url")
+        .help("The base URL for absolute links, such as in feeds.\nExample: --base-url \"https://example.com\"")
+        .long("base-url")
+        .value_name("URL")
+        .value_parser(clap::value_parser!(String))
+    )
+    .arg(Arg::new("data")
+        .help("A data directory of JSON, TOML, YAML, and CSV files, for the template variable `data`.\nDefault: \"data\" in the site root, which is the parent of the input directory, if it exists.\nExample: --data \"data/\" …")
+        .short('d')
+        .long("data")
+        .value_name("DIRECTORY")
+        .value_parser(clap::value_parser!(PathBuf))
+    )
+    .arg(Arg::new("description_length")
+        .help("The description length, i.e. the most characters in the description, which is truncated on a word boundary.\nDefault: 160.\nExample: --description-length 120")
+        .long("description-length")
+        .value_name("LENGTH")
+        .value_parser(clap::value_parser!(usize))
+    )
+    .arg(Arg::new("drafts")
+        .help("Build draft pages, i.e. with front matter `draft: true`, which are skipped by default.\nExample: --drafts")
+        .long("drafts")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("env")
+        .help("An environment variable name to expose in the template variable `env`, without the prefix.\nExample: --env analytics_id … means SITA_ANALYTICS_ID becomes env.analytics_id")
+        .long("env")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+        .num_args(1..)
+    )
+    .arg(Arg::new("env_prefix")
+        .help("The environment variable name prefix.\nDefault: \"SITA_\".\nExample: --env-prefix \"SITE_\"")
+        .long("env-prefix")
+        .value_name("PREFIX")
+        .value_parser(clap::value_parser!(String))
+    )
+    .arg(Arg::new("feeds")
+        .help("Write feeds for the site and for each section: Atom \"feed.xml\", RSS \"rss.xml\", and JSON Feed \"feed.json\".\nExample: --feeds")
+        .long("feeds")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("feed_full_content")
+        .help("Put each page's full content in its feed entry, rather than only its summary.\nExample: --feed-full-content")
+        .long("feed-full-content")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("feed_length")
+        .help("The feed length, i.e. the most entries per feed, newest first.\nDefault: 20.\nExample: --feed-length 10")
+        .long("feed-length")
+        .value_name("LENGTH")
+        .value_parser(clap::value_parser!(usize))
+    )
+    .arg(Arg::new("future")
+        .help("Build future pages, i.e. with front matter `publish_date`, or else `date`, after now, which are skipped by default.\nExample: --future")
+        .long("future")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("language")
+        .help("A site language code; the first is the default language.\nA file name such as \"about.fr.md\" is a language variant, which outputs to \"fr/about.html\".\nExample: --language en fr de es")
+        .long("language")
+        .value_name("CODE")
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+        .num_args(1..)
+    )
+    .arg(Arg::new("not_found_template")
+        .help("The template name for the not found page \"404.html\", which is \"404.md\" if any, or else a generated page.\nExample: --not-found-template 404.html")
+        .long("not-found-template")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+    )
+    .arg(Arg::new("paginate_by")
+        .help("The most items per chunk of a listing page, such as a section index page or term page.\nA page's front matter `paginate_by` wins.\nExample: --paginate-by 10")
+        .long("paginate-by")
+        .value_name("NUMBER")
+        .value_parser(clap::value_parser!(usize))
+    )
+    .arg(Arg::new("permalink")
+        .help("Set the URL pattern of a section directory's pages, with the tokens :year, :month, :day, :slug, and :title.\nA page's front matter `url` wins.\nExample: --permalink posts \"/blog/:year/:month/:slug/\" …")
+        .long("permalink")
+        .num_args(2)
+        .value_names(["SECTION", "PATTERN"])
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+    )
+    .arg(Arg::new("redirect")
+        .help("Redirect an old URL to a new URL, in addition to each page's front matter `aliases`.\nExample: --redirect \"/blog/\" \"/posts/\" …")
+        .long("redirect")
+        .num_args(2)
+        .value_names(["FROM", "TO"])
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+    )
+    .arg(Arg::new("redirects_file")
+        .help("Write \"_redirects\", with every redirect, for hosts such as Netlify and Cloudflare Pages.\nExample: --redirects-file")
+        .long("redirects-file")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("related_count")
+        .help("The most pages in each page's variable `related`, which are the pages that share the most taxonomy terms.\nDefault: 5.\nExample: --related-count 3")
+        .long("related-count")
+        .value_name("COUNT")
+        .value_parser(clap::value_parser!(usize))
+    )
+    .arg(Arg::new("remove_title")
+        .help("Remove the first headline from the content, so a template can show the title once.\nExample: --remove-title")
+        .long("remove-title")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("robots")
+        .help("Write \"robots.txt\", with any disallow rules, and with a link to the sitemap, if any.\nExample: --robots")
+        .long("robots")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("robots_disallow")
+        .help("A URL path prefix that \"robots.txt\" disallows for all user agents.\nExample: --robots-disallow \"/drafts/\" …")
+        .long("robots-disallow")
+        .value_name("PATH")
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+        .num_args(1..)
+    )
+    .arg(Arg::new("search")
+        .help("Write \"search-index.json\", with each listed page's URL, title, headings, and plain text without stop words.\nExample: --search")
+        .long("search")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("search_field")
+        .help("A field of each page in the search index, such as a front matter key.\nDefault: url title headings text.\nExample: --search-field url title description …")
+        .long("search-field")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+        .num_args(1..)
+    )
+    .arg(Arg::new("search_inverted_index")
+        .help("Add a prebuilt inverted index to the search index, which maps each word to its pages.\nExample: --search-inverted-index")
+        .long("search-inverted-index")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("search_section")
+        .help("A section directory whose pages, including its subsections, are in the search index.\nDefault: every listed page.\nExample: --search-section docs …")
+        .long("search-section")
+        .value_name("PATH")
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+        .num_args(1..)
+    )
     .arg(Arg::new("set")
-        .help("Set a variable name to a value.\nExample: --set pi 3.1415 …")
+        .help("Set a variable name to a value.\nThe name can be a dotted path for nesting.\nExample: --set pi 3.1415 …\nExample: --set site.author.name \"Ada\" …")
         .short('s')
         .long("set")
         .num_args(2)
@@ -78,6 +251,39 @@ pub fn app() -> Command {
         .value_parser(clap::value_parser!(String))
         .action(clap::ArgAction::Append)
     )
+    .arg(Arg::new("set_json")
+        .help("Set a variable name to a JSON value, such as a number, boolean, or list.\nThe name can be a dotted path for nesting.\nExample: --set-json count 3 …\nExample: --set-json site.tags '[\"alfa\", \"bravo\"]' …")
+        .long("set-json")
+        .num_args(2)
+        .value_names(["NAME", "JSON"])
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+    )
+    .arg(Arg::new("sitemap")
+        .help("Write \"sitemap.xml\", with every rendered page's absolute URL and last modified date.\nExample: --sitemap")
+        .long("sitemap")
+        .action(clap::ArgAction::SetTrue)
+    )
+    .arg(Arg::new("taxonomy")
+        .help("Taxonomy names, which are also front matter keys, to generate term pages.\nExample: --taxonomy tags categories")
+        .long("taxonomy")
+        .value_name("NAME")
+        .num_args(1..)
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+    )
+    .arg(Arg::new("taxonomy_template")
+        .help("The template name for each page that lists a taxonomy's terms.\nDefault: the page template.\nExample: --taxonomy-template taxonomy.html")
+        .long("taxonomy-template")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+    )
+    .arg(Arg::new("term_template")
+        .help("The template name for each page that lists a term's pages.\nDefault: the page template.\nExample: --term-template term.html")
+        .long("term-template")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+    )
     .arg(Arg::new("test")
         .help("Print test output for debugging, verifying, tracing, and the like.\nExample: --test")
         .long("test")
@@ -90,6 +296,12 @@ pub fn app() -> Command {
         .value_parser(clap::value_parser!(PathBuf))
         .action(clap::ArgAction::Append)
     )
+    .arg(Arg::new("toc_depth")
+        .help("The table of contents depth, i.e. the deepest heading level to include, from 1 to 6.\nDefault: 3.\nExample: --toc-depth 2")
+        .long("toc-depth")
+        .value_name("LEVEL")
+        .value_parser(clap::value_parser!(u8).range(1..=6))
+    )
     .arg(Arg::new("verbose")
         .help("Set the verbosity level: 0=none, 1=error, 2=warn, 3=info, 4=debug, 5=trace.\nExample: --verbose …")
         .short('v')
@@ -124,6 +336,36 @@ pub fn args() -> Args {
         _ => None,
     };
 
+    let data_dir: Option<PathBuf> = matches.get_one::<PathBuf>("data").cloned();
+
+    let env_list: Option<List<String>> = matches.get_many::<String>("env").map(|x| x.cloned().collect());
+
+    let env_prefix: Option<String> = matches.get_one::<String>("env_prefix").cloned();
+
+    let archive = matches.get_flag("archive");
+
+    let archive_template: Option<String> = matches.get_one::<String>("archive_template").cloned();
+
+    let archive_year_template: Option<String> = matches.get_one::<String>("archive_year_template").cloned();
+
+    let archive_month_template: Option<String> = matches.get_one::<String>("archive_month_template").cloned();
+
+    let base_url: Option<String> = matches.get_one::<String>("base_url").cloned();
+
+    let description_length: Option<usize> = matches.get_one::<usize>("description_length").copied();
+
+    let drafts = matches.get_flag("drafts");
+
+    let feeds = matches.get_flag("feeds");
+
+    let feed_length: Option<usize> = matches.get_one::<usize>("feed_length").copied();
+
+    let feed_full_content = matches.get_flag("feed_full_content");
+
+    let future = matches.get_flag("future");
+
+    let toc_depth: Option<u8> = matches.get_one::<u8>("toc_depth").copied();
+
     let settings = match matches.get_occurrences("set") {
         Some(occurrences) => {
             // TODO: refactor & optimize
@@ -140,11 +382,70 @@ pub fn args() -> Args {
         None => None,
     };
 
+    let settings_json: Option<Map<String, String>> = matches.get_occurrences::<String>("set_json").map(|occurrences|
+        occurrences.map(|mut occurrence|
+            (
+                occurrence.next().cloned().unwrap_or_default(),
+                occurrence.next().cloned().unwrap_or_default(),
+            )
+        ).collect()
+    );
+
     let template_list: Option<List<PathBuf>> = match matches.get_many("template") {
         Some(paths) => Some(paths.map(|path: &PathBuf| path.to_owned()).collect()),
         _ => None,
     };
 
+    let language_list: Option<List<String>> = matches.get_many::<String>("language").map(|x| x.cloned().collect());
+
+    let not_found_template: Option<String> = matches.get_one::<String>("not_found_template").cloned();
+
+    let paginate_by: Option<usize> = matches.get_one::<usize>("paginate_by").copied();
+
+    let permalink_map: Option<Map<String, String>> = matches.get_occurrences::<String>("permalink").map(|occurrences|
+        occurrences.map(|mut occurrence|
+            (
+                occurrence.next().cloned().unwrap_or_default(),
+                occurrence.next().cloned().unwrap_or_default(),
+            )
+        ).collect()
+    );
+
+    let redirect_map: Option<Map<String, String>> = matches.get_occurrences::<String>("redirect").map(|occurrences|
+        occurrences.map(|mut occurrence|
+            (
+                occurrence.next().cloned().unwrap_or_default(),
+                occurrence.next().cloned().unwrap_or_default(),
+            )
+        ).collect()
+    );
+
+    let redirects_file = matches.get_flag("redirects_file");
+
+    let related_count: Option<usize> = matches.get_one::<usize>("related_count").copied();
+
+    let remove_title = matches.get_flag("remove_title");
+
+    let robots = matches.get_flag("robots");
+
+    let robots_disallow_list: Option<List<String>> = matches.get_many::<String>("robots_disallow").map(|x| x.cloned().collect());
+
+    let search = matches.get_flag("search");
+
+    let search_field_list: Option<List<String>> = matches.get_many::<String>("search_field").map(|x| x.cloned().collect());
+
+    let search_inverted_index = matches.get_flag("search_inverted_index");
+
+    let search_section_list: Option<List<String>> = matches.get_many::<String>("search_section").map(|x| x.cloned().collect());
+
+    let sitemap = matches.get_flag("sitemap");
+
+    let taxonomy_list: Option<List<String>> = matches.get_many::<String>("taxonomy").map(|x| x.cloned().collect());
+
+    let taxonomy_template: Option<String> = matches.get_one::<String>("taxonomy_template").cloned();
+
+    let term_template: Option<String> = matches.get_one::<String>("term_template").cloned();
+
     let test = matches.get_flag("test");
 
     let log_level = match matches.get_count("verbose") {
@@ -162,9 +463,43 @@ pub fn args() -> Args {
         log_level: log_level,
         output_list: output_list,
         output_file_name_extension: output_file_name_extension,
+        toc_depth,
         settings: settings,
+        settings_json,
         template_list: template_list,
         extra_list: extra_list,
+        data_dir,
+        env_prefix,
+        env_list,
+        archive,
+        archive_template,
+        archive_year_template,
+        archive_month_template,
+        base_url,
+        description_length,
+        drafts,
+        feeds,
+        feed_length,
+        feed_full_content,
+        future,
+        language_list,
+        not_found_template,
+        paginate_by,
+        permalink_map,
+        redirect_map,
+        redirects_file,
+        related_count,
+        remove_title,
+        robots,
+        robots_disallow_list,
+        search,
+        search_field_list,
+        search_inverted_index,
+        search_section_list,
+        sitemap,
+        taxonomy_list,
+        taxonomy_template,
+        term_template,
         test: test,
     };
 
@@ -319,6 +654,120 @@ mod tests {
         assert_command_stdout_contains!(command, &target);
     }
 
+    #[test]
+    fn test_data() {
+        let target = r#" data_dir: Some("alfa")"#;
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "-d", "alfa"], &target);
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--data", "alfa"], &target);
+    }
+
+    #[test]
+    fn test_env() {
+        let mut command = std::process::Command::new(&*COMMAND_OS);
+        command.args(["--test", "--env", "alfa", "bravo", "--env-prefix", "CHARLIE_"]);
+        assert_command_stdout_contains!(command, r#" env_prefix: Some("CHARLIE_"), env_list: Some(["alfa", "bravo"])"#);
+    }
+
+    #[test]
+    fn test_archive() {
+        assert_program_args_stdout_contains!(
+            &*COMMAND_OS,
+            &["--test", "--archive", "--archive-template", "a.html", "--archive-year-template", "y.html", "--archive-month-template", "m.html"],
+            r#" archive: true, archive_template: Some("a.html"), archive_year_template: Some("y.html"), archive_month_template: Some("m.html")"#
+        );
+    }
+
+    #[test]
+    fn test_base_url() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--base-url", "https://example.com"], r#" base_url: Some("https://example.com")"#);
+    }
+
+    #[test]
+    fn test_description_length() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--description-length", "120"], r#" description_length: Some(120)"#);
+    }
+
+    #[test]
+    fn test_drafts_and_future() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--drafts"], r#" drafts: true"#);
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--future"], r#" future: true"#);
+    }
+
+    #[test]
+    fn test_language() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--language", "en", "fr"], r#" language_list: Some(["en", "fr"])"#);
+    }
+
+    #[test]
+    fn test_not_found_template() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--not-found-template", "404.html"], r#" not_found_template: Some("404.html")"#);
+    }
+
+    #[test]
+    fn test_paginate_by() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--paginate-by", "10"], r#" paginate_by: Some(10)"#);
+    }
+
+    #[test]
+    fn test_permalink() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--permalink", "posts", "/blog/:year/:slug/"], r#" permalink_map: Some({"posts": "/blog/:year/:slug/"})"#);
+    }
+
+    #[test]
+    fn test_redirect() {
+        assert_program_args_stdout_contains!(
+            &*COMMAND_OS,
+            &["--test", "--redirect", "/blog/", "/posts/", "--redirect", "/a.html", "/b.html", "--redirects-file"],
+            r#" redirect_map: Some({"/a.html": "/b.html", "/blog/": "/posts/"}), redirects_file: true"#
+        );
+    }
+
+    #[test]
+    fn test_feeds() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--feeds", "--feed-length", "10", "--feed-full-content"], r#" feeds: true, feed_length: Some(10), feed_full_content: true"#);
+    }
+
+    #[test]
+    fn test_robots() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--robots", "--robots-disallow", "/drafts/", "/tmp/"], r#" robots_disallow_list: Some(["/drafts/", "/tmp/"]), robots: true"#);
+    }
+
+    #[test]
+    fn test_search() {
+        assert_program_args_stdout_contains!(
+            &*COMMAND_OS,
+            &["--test", "--search", "--search-field", "url", "title", "--search-inverted-index", "--search-section", "docs"],
+            r#" search: true, search_field_list: Some(["url", "title"]), search_inverted_index: true, search_section_list: Some(["docs"])"#
+        );
+    }
+
+    #[test]
+    fn test_sitemap() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--sitemap"], r#" sitemap: true"#);
+    }
+
+    #[test]
+    fn test_related_count() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--related-count", "3"], r#" related_count: Some(3)"#);
+    }
+
+    #[test]
+    fn test_remove_title() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--remove-title"], r#" remove_title: true"#);
+    }
+
+    #[test]
+    fn test_taxonomy() {
+        let mut command = std::process::Command::new(&*COMMAND_OS);
+        command.args(["--test", "--taxonomy", "tags", "categories", "--taxonomy-template", "alfa.html", "--term-template", "bravo.html"]);
+        assert_command_stdout_contains!(command, r#" taxonomy_list: Some(["tags", "categories"]), taxonomy_template: Some("alfa.html"), term_template: Some("bravo.html")"#);
+    }
+
+    #[test]
+    fn test_toc_depth() {
+        assert_program_args_stdout_contains!(&*COMMAND_OS, &["--test", "--toc-depth", "2"], r#" toc_depth: Some(2)"#);
+    }
+
     #[test]
     fn test_template() {
 
@@ -417,4 +866,12 @@ mod tests {
         assert_command_stdout_contains!(command, &target);
     }
 
+    #[test]
+    fn test_set_json() {
+        let mut command = std::process::Command::new(&*COMMAND_OS);
+        command.args(["--test", "--set-json", "alfa", "1", "--set-json", "bravo.charlie", "[true]"]);
+        let target = r#" settings_json: Some({"alfa": "1", "bravo.charlie": "[true]"})"#;
+        assert_command_stdout_contains!(command, &target);
+    }
+
 }
\ No newline at end of file
@@ -3,7 +3,15 @@
 use std::path::PathBuf;
 use crate::app::args::Args;
 use crate::app::config::Config;
-use crate::types::list::*;
+use crate::types::{list::*, set::*};
+use crate::defaults::defaults_declaration::DefaultsDeclaration;
+use crate::f::from_data_dir_and_input_into_data_dir_path_buf::*;
+use crate::f::from_data_dir_into_json_value::*;
+use crate::f::from_env_vars_into_map_string_string::*;
+use crate::f::from_relative_path_into_url_string::*;
+use crate::state::state_trait::StateTrait;
+use crate::state::state_value::StateValue;
+use crate::schema::schema_declaration::SchemaDeclaration;
 use crate::templater::templater_trait::TemplaterTrait;
 use crate::templater::templater_with_handlebars::TemplaterWithHandlebars;
 //use crate::templater::templater_with_askama::TemplaterWithAskama;
@@ -20,6 +28,8 @@ use crate::templater::templater_with_handlebars::TemplaterWithHandlebars;
 ///
 ///   * Initialize templating.
 ///
+///   * Initialize defaults, such as the `data` variable.
+///
 //    * Process each page.
 ///
 /// Example:
@@ -34,7 +44,8 @@ pub(crate) fn run() -> Result<(), Error> {
     let _config = initialize_configuration()?;
     let args = initialize_arguments();
     let templater = initialize_templater(&args)?;
-    cook_all(&args, &templater)
+    let defaults = initialize_defaults(&args)?;
+    cook_all(&args, &templater, defaults.as_ref())
 }
 
 fn initialize_configuration() -> Result<Config, Error> {
@@ -145,9 +156,78 @@ fn initialize_templater_helpers(
     Ok(())
 }
 
+/// Initialize the site-wide defaults that every page's state starts with.
+///
+/// The data directory, if any, becomes the variable `data`,
+/// such as `data/authors.yaml` becoming `data.authors`.
+///
+/// The allowed environment variables, if any, become the variable `env`,
+/// such as `SITA_ANALYTICS_ID` becoming `env.analytics_id`.
+///
+/// The settings, if any, are set by dotted key path, such as
+/// `--set site.author.name Ada` becoming `site.author.name`.
+///
+fn initialize_defaults(
+    args: &Args,
+) -> Result<Option<DefaultsDeclaration>, Error> {
+    trace!("initialize_defaults");
+    let mut defaults: Option<DefaultsDeclaration> = None;
+    if let Some(data) = initialize_defaults_data(args)? {
+        defaults.get_or_insert_with(DefaultsDeclaration::default)
+        .state.insert_value(String::from("data"), StateValue::from(data));
+    }
+    if let Some(env_list) = &args.env_list {
+        let prefix = args.env_prefix.as_deref().unwrap_or(crate::app::args::ENV_PREFIX_AS_STR);
+        let names: Set<String> = env_list.iter().cloned().collect();
+        let env = from_env_vars_into_map_string_string(std::env::vars_os(), prefix, &names);
+        defaults.get_or_insert_with(DefaultsDeclaration::default)
+        .state.insert_value(String::from("env"), StateValue::Map(env.into_iter().map(|(k, v)| (k, StateValue::String(v))).collect()));
+    }
+    if let Some(settings) = &args.settings {
+        for (key_path, value) in settings.iter() {
+            defaults.get_or_insert_with(DefaultsDeclaration::default)
+            .state.insert_value_at_key_path(key_path, StateValue::from(value.as_str()));
+        }
+    }
+    if let Some(settings_json) = &args.settings_json {
+        for (key_path, text) in settings_json.iter() {
+            let value: serde_json::Value = serde_json::from_str(text)
+            .map_or_else(
+                |err| Err(Error::SetJson { key_path: key_path.to_owned(), text: text.to_owned(), err }),
+                Ok
+            )?;
+            defaults.get_or_insert_with(DefaultsDeclaration::default)
+            .state.insert_value_at_key_path(key_path, StateValue::from(value));
+        }
+    }
+    Ok(defaults)
+}
+
+fn initialize_defaults_data(
+    args: &Args,
+) -> Result<Option<serde_json::Value>, Error> {
+    trace!("initialize_defaults_data");
+    // The default data directory is in the site root of the first input.
+    let data_dir = match args.input_list.as_ref().and_then(|x| x.first()) {
+        Some(input) => from_data_dir_and_input_into_data_dir_path_buf(args.data_dir.as_deref(), input),
+        None => args.data_dir.to_owned(),
+    };
+    match data_dir {
+        Some(data_dir) => {
+            from_data_dir_into_json_value(&data_dir)
+            .map_or_else(
+                |err| Err(Error::Data(err)),
+                |x| Ok(Some(x))
+            )
+        },
+        None => Ok(None),
+    }
+}
+
 fn cook_all(
     args: &Args, 
-    templater: &TemplaterWithHandlebars
+    templater: &TemplaterWithHandlebars,
+    defaults: Option<&DefaultsDeclaration>,
 ) -> Result<(), Error> {
     trace!("cook_all ➡ args.input_list: {:?}, args.output_list: {:?}", &args.input_list, &args.output_list);
     if let (
@@ -162,7 +242,7 @@ fn cook_all(
         for i in 0..input_list.len() {
             let input = &input_list[i];
             let output = &output_list[i];
-            cook_one(args, Some(templater), input, output)?
+            cook_one(args, Some(templater), defaults, input, output)?
         }
     } else {
         trace!("cook_all ➡ missing input/output lists");
@@ -185,6 +265,7 @@ fn vet_input_output_list_length(input_list: &List<PathBuf>, output_list: &List<P
 fn cook_one(
     args: &Args, 
     templater: Option<&TemplaterWithHandlebars>, 
+    defaults: Option<&DefaultsDeclaration>,
     input: &PathBuf, 
     output: &PathBuf
 ) -> Result<(), Error> {
@@ -198,6 +279,7 @@ fn cook_one(
         return crate::cook_dir::cook_dir(
             &args,
             templater,
+            defaults,
             input,
             output,
         ).map_or_else(
@@ -206,11 +288,35 @@ fn cook_one(
         )
     }
     if input.is_file() {
+        let schema = match input.parent() {
+            Some(dir) => SchemaDeclaration::load_from_dir(&dir.to_path_buf())
+                .map_or_else(
+                    |err| Err(Error::SchemaDeclaration(err)),
+                    Ok
+                )?,
+            None => None,
+        };
+        let dir_defaults = match input.parent() {
+            Some(dir) => DefaultsDeclaration::load_from_dir(&dir.to_path_buf())
+                .map_or_else(
+                    |err| Err(Error::DefaultsDeclaration(err)),
+                    Ok
+                )?,
+            None => None,
+        };
+        let defaults = match (defaults, dir_defaults) {
+            (Some(x), Some(y)) => Some(x.cascade(&y)),
+            (Some(x), None) => Some(x.to_owned()),
+            (None, y) => y,
+        };
         return crate::cook_file::cook_file(
             &args,
             templater,
+            schema.as_ref(),
+            defaults.as_ref(),
             input,
             output,
+            &from_relative_path_into_url_string(output.file_name().map(std::path::Path::new).unwrap_or(output)),
         )
         .map_or_else(
             |err| Err(Error::CookFile(err)),
@@ -271,6 +377,22 @@ pub enum Error {
         input: PathBuf,
     },    
 
+    #[error("Data ➡ {0:?}")]
+    Data(crate::f::from_data_dir_into_json_value::Error),
+
+    #[error("SetJson ➡ key_path: {key_path:?}, text: {text:?}, err: {err:?}")]
+    SetJson {
+        key_path: String,
+        text: String,
+        err: serde_json::Error,
+    },
+
+    #[error("DefaultsDeclaration ➡ {0:?}")]
+    DefaultsDeclaration(crate::defaults::defaults_declaration::Error),
+
+    #[error("SchemaDeclaration ➡ {0:?}")]
+    SchemaDeclaration(crate::schema::schema_declaration::Error),
+
     #[error("CookDir ➡ {0:?}")]
     CookDir(crate::cook_dir::Error),
 
@@ -1,44 +1,136 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use walkdir::WalkDir;
 use crate::app::args::Args;
+use crate::types::{list::*, map::*};
+use crate::state::state_value::StateValue;
+use crate::defaults::defaults_declaration::DefaultsDeclaration;
+use crate::schema::schema_declaration::SchemaDeclaration;
+use crate::schema::schema_violation::SchemaViolation;
 use crate::templater::templater_trait::TemplaterTrait;
-use crate::cook_file::cook_file;
+use crate::cook_file::{prepare_page, render_site_model};
+use crate::site::site_page::{is_not_found_path, is_section_index_path, SitePage};
+use crate::site::site_feed::*;
+use crate::site::site_language::*;
+use crate::site::site_model::{SiteModel, RELATED_COUNT_DEFAULT};
+use crate::site::site_permalink::*;
+use crate::site::site_redirect::*;
+use crate::site::site_search::*;
+use crate::site::site_sitemap::*;
+use crate::f::from_data_dir_and_input_into_data_dir_path_buf::*;
+use crate::f::from_relative_path_into_url_string::*;
+use crate::f::from_url_into_relative_path_buf::*;
 
+/// Cook a directory, in two phases.
+///
+/// The first phase walks the directory and prepares each page,
+/// such as its front matter, defaults, and computed variables.
+///
+/// The second phase renders each page, with the variable `site`
+/// that lists every page, so templates can build indexes and menus.
+///
+/// A section index page, such as "posts/_index.md", renders to
+/// "posts/index.html", with the variable `section`.
+///
 pub (crate) fn cook_dir<T: TemplaterTrait> (
     args: &Args, 
     templater: Option<&T>, 
+    defaults: Option<&DefaultsDeclaration>,
     input: &PathBuf, 
     output: &PathBuf
 ) -> Result<(), Error> {
     trace!("Cook directory.");
-    trace!("cook_dir ➡ args: {:?}, templater: {:?}, input: {:?}, output: {:?}", args, templater, input, output);
+    trace!("cook_dir ➡ args: {:?}, templater: {:?}, defaults: {:?}, input: {:?}, output: {:?}", args, templater, defaults, input, output);
     vet_input(input)?;
     vet_output(output)?;
+    vet_sitemap(args)?;
     let output_file_name_extension = match &args.output_file_name_extension {
         Some(x) => x,
         None => &crate::app::args::OUTPUT_FILE_NAME_EXTENSION_AS_PATH_BUF,
     };
-    for dir_entry in WalkDir::new(&input) {
+    let languages = args.language_list.as_deref().unwrap_or_default();
+    // Schema declarations by relative directory path; each directory inherits its parent's.
+    let mut schemas: Map<PathBuf, Option<SchemaDeclaration>> = Map::new();
+    // Defaults declarations by relative directory path; each directory deep merges over its parent's.
+    let mut defaults_by_dir: Map<PathBuf, Option<DefaultsDeclaration>> = Map::new();
+    let mut violations: List<SchemaViolation> = List::new();
+    // Site model of every prepared page, for rendering after the walk.
+    let mut site_model = SiteModel::default();
+    let now = SystemTime::now();
+    // The data directory may be inside the input, such as "input/data", and its files aren't pages.
+    let data_dir = from_data_dir_and_input_into_data_dir_path_buf(args.data_dir.as_deref(), input)
+        .and_then(|x| x.canonicalize().ok());
+    let walk = WalkDir::new(&input).sort_by_file_name().into_iter().filter_entry(|dir_entry|
+        data_dir.is_none() || !dir_entry.file_type().is_dir() || dir_entry.path().canonicalize().ok() != data_dir
+    );
+    for dir_entry in walk {
         match dir_entry {
             Ok(dir_entry) => {
                 let file_type = dir_entry.file_type();
                 debug!("cook_dir ➡ dir_entry: {:?}, file_type: {:?}", dir_entry, file_type);
                 if file_type.is_file() {
+                    if SchemaDeclaration::is_schema_file_name(&dir_entry.file_name().to_string_lossy()) {
+                        trace!("cook_dir ➡ skip schema file: {:?}", dir_entry);
+                        continue;
+                    }
+                    if DefaultsDeclaration::is_defaults_file_name(&dir_entry.file_name().to_string_lossy()) {
+                        trace!("cook_dir ➡ skip defaults file: {:?}", dir_entry);
+                        continue;
+                    }
                     match dir_entry.path().strip_prefix(&input) {
                         Ok(path) => {
                             let input_entry = input.join(path);
-                            let mut output_entry = output.join(path); 
-                            output_entry.set_extension(output_file_name_extension);
-                            cook_file(
-                                &args,
-                                templater,
+                            // A language variant, such as "about.fr.md", becomes "fr/about.md".
+                            let (language, page_path) = from_path_and_languages_into_language_and_path(path, languages);
+                            // A section index page, such as "posts/_index.md", becomes "posts/index.html".
+                            let output_path = match is_section_index_path(&page_path) {
+                                true => page_path.with_file_name(INDEX_FILE_NAME_AS_STR).with_extension(output_file_name_extension),
+                                false => page_path.with_extension(output_file_name_extension),
+                            };
+                            let output_entry = output.join(&output_path);
+                            let url = from_relative_path_into_url_string(&output_path);
+                            let schema = path.parent()
+                                .and_then(|parent| schemas.get(parent))
+                                .and_then(|x| x.as_ref());
+                            let defaults = path.parent()
+                                .and_then(|parent| defaults_by_dir.get(parent))
+                                .and_then(|x| x.as_ref());
+                            // A permalink pattern, such as "/blog/:slug/", is for a section's pages,
+                            // in the language's own output root, such as "/fr/blog/:slug/".
+                            // A section index page and the not found page keep their own URLs.
+                            let permalink = match (&args.permalink_map, path.parent()) {
+                                (Some(permalink_map), Some(parent)) if !is_section_index_path(&page_path) && !is_not_found_path(&page_path) =>
+                                    from_permalink_map_and_dir_and_language_into_pattern(permalink_map, parent, language, languages),
+                                _ => None,
+                            };
+                            match prepare_page(
+                                args,
+                                schema,
+                                defaults,
                                 &input_entry,
                                 &output_entry,
-                            )
-                            .map_or_else(
-                                |err| Err(Error::CookFile(err)),
-                                |()| Ok(())
-                            )?
+                                &page_path,
+                                &url,
+                                permalink.as_deref(),
+                            ) {
+                                Ok(mut page) => match page.is_published(args.drafts, args.future, now) {
+                                    true => {
+                                        relocate_page(&mut page, output, output_file_name_extension, &url);
+                                        if !language.is_empty() {
+                                            page.state.insert_value(String::from("lang"), StateValue::from(language));
+                                        }
+                                        site_model.pages.push(page)
+                                    },
+                                    false => trace!("cook_dir ➡ skip unpublished page: {:?}", path),
+                                },
+                                Err(crate::cook_file::Error::Schema { input: _, violations: x }) => {
+                                    x.iter().for_each(|violation| error!("{}", violation));
+                                    violations.extend(x);
+                                },
+                                Err(err) => {
+                                    return Err(Error::CookFile(err))
+                                }
+                            }
                         },
                         Err(error) => {
                             return Err(Error::StripPrefixError {
@@ -53,6 +145,34 @@ pub (crate) fn cook_dir<T: TemplaterTrait> (
                     trace!("cook_dir ➡ input: {:?}, output: {:?}, dir entry is a dir", input, output);
                     match dir_entry.path().strip_prefix(&input) {
                         Ok(path) => {
+                            let schema = match SchemaDeclaration::load_from_dir(&dir_entry.path().to_path_buf())
+                            .map_or_else(
+                                |err| Err(Error::SchemaDeclaration(err)),
+                                Ok
+                            )? {
+                                Some(x) => Some(x),
+                                None => path.parent()
+                                    .and_then(|parent| schemas.get(parent))
+                                    .and_then(|x| x.to_owned()),
+                            };
+                            schemas.insert(path.to_path_buf(), schema);
+                            let parent_defaults = match path.parent() {
+                                Some(parent) => defaults_by_dir.get(parent).and_then(|x| x.as_ref()),
+                                None => defaults,
+                            };
+                            let defaults = match (
+                                parent_defaults,
+                                DefaultsDeclaration::load_from_dir(&dir_entry.path().to_path_buf())
+                                .map_or_else(
+                                    |err| Err(Error::DefaultsDeclaration(err)),
+                                    Ok
+                                )?
+                            ) {
+                                (Some(parent), Some(x)) => Some(parent.cascade(&x)),
+                                (Some(parent), None) => Some(parent.to_owned()),
+                                (None, x) => x,
+                            };
+                            defaults_by_dir.insert(path.to_path_buf(), defaults);
                             let output_entry = output.join(path); 
                             std::fs::create_dir_all(output_entry)
                             .map_or_else(
@@ -78,9 +198,217 @@ pub (crate) fn cook_dir<T: TemplaterTrait> (
             }
         }
     }
+    if let Some(language_list) = &args.language_list {
+        site_model.insert_translations(language_list);
+    }
+    if let Some(taxonomy_list) = &args.taxonomy_list {
+        site_model.insert_taxonomies(
+            taxonomy_list,
+            output,
+            output_file_name_extension,
+            args.taxonomy_template.as_deref(),
+            args.term_template.as_deref(),
+        );
+        site_model.insert_related(args.related_count.unwrap_or(RELATED_COUNT_DEFAULT));
+    }
+    if args.archive {
+        site_model.insert_archive(
+            output,
+            output_file_name_extension,
+            args.archive_template.as_deref(),
+            args.archive_year_template.as_deref(),
+            args.archive_month_template.as_deref(),
+        );
+    }
+    if let Some(not_found_template) = &args.not_found_template {
+        site_model.insert_not_found_page(output, output_file_name_extension, not_found_template);
+    }
+    vet_site_model_outputs(&site_model)?;
+    if args.feeds {
+        write_feeds(args, &site_model, output)?;
+    }
+    render_site_model(args, templater, &mut site_model)
+    .map_or_else(
+        |err| Err(Error::CookFile(err)),
+        Ok
+    )?;
+    if args.sitemap {
+        write_sitemap(args, &site_model, output)?;
+    }
+    if args.robots {
+        write_robots(args, output)?;
+    }
+    if args.search {
+        write_search_index(args, &site_model, output)?;
+    }
+    write_redirects(args, &site_model, output, output_file_name_extension)?;
+    if !violations.is_empty() {
+        return Err(Error::Schema { violations })
+    }
+    Ok(())
+}
+
+/// Relocate a page's output path to its `url` variable, when it differs
+/// from the URL of its input path, such as via front matter or a permalink.
+fn relocate_page(
+    page: &mut SitePage,
+    output: &Path,
+    extension: &Path,
+    url: &str,
+) {
+    let page_url = page.state.get_value("url").map(|x| x.to_plain_string()).unwrap_or_default();
+    if page_url == url {
+        return
+    }
+    trace!("relocate_page ➡ input: {:?}, url: {:?}", page.input, page_url);
+    match from_url_into_relative_path_buf(&page_url, extension) {
+        Some(path) => {
+            page.output = output.join(path);
+            page.state.insert_value(String::from("output_path"), StateValue::from(page.output.to_string_lossy().as_ref()));
+        },
+        None => warn!("relocate_page ➡ keep the output path, because the URL isn't in the output directory: {:?}", page_url),
+    }
+}
+
+/// Write the feeds of the site model, in each feed's output directory.
+fn write_feeds(
+    args: &Args,
+    site_model: &SiteModel,
+    output: &Path,
+) -> Result<(), Error> {
+    trace!("write_feeds ➡ output: {:?}", output);
+    let base_url = args.base_url.as_deref();
+    let full_content = args.feed_full_content;
+    for feed in site_model.feeds(args.feed_length.unwrap_or(FEED_LENGTH_DEFAULT)) {
+        // A section can exist only in a language that has no rendered pages yet, such as "fr/posts".
+        let dir = output.join(&feed.path);
+        std::fs::create_dir_all(&dir)
+        .map_or_else(
+            |err| Err(Error::IO(err)),
+            Ok
+        )?;
+        let contents = [
+            (FEED_ATOM_FILE_NAME_AS_STR, feed.to_atom_string(base_url, full_content).map_or_else(|err| Err(Error::Feed(err)), Ok)?),
+            (FEED_RSS_FILE_NAME_AS_STR, feed.to_rss_string(base_url, full_content).map_or_else(|err| Err(Error::Feed(err)), Ok)?),
+            (FEED_JSON_FILE_NAME_AS_STR, feed.to_json_string(base_url, full_content)),
+        ];
+        for (file_name, contents) in contents {
+            std::fs::write(dir.join(file_name), contents)
+            .map_or_else(
+                |err| Err(Error::IO(err)),
+                Ok
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the sitemap of every rendered page and listing, in the output directory.
+fn write_sitemap(
+    args: &Args,
+    site_model: &SiteModel,
+    output: &Path,
+) -> Result<(), Error> {
+    trace!("write_sitemap ➡ output: {:?}", output);
+    let files = site_model.sitemap().to_files(args.base_url.as_deref(), SITEMAP_URL_LIMIT)
+    .map_or_else(
+        |err| Err(Error::Sitemap(err)),
+        Ok
+    )?;
+    for (file_name, contents) in files {
+        std::fs::write(output.join(file_name), contents)
+        .map_or_else(
+            |err| Err(Error::IO(err)),
+            Ok
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the robots file, in the output directory.
+fn write_robots(
+    args: &Args,
+    output: &Path,
+) -> Result<(), Error> {
+    trace!("write_robots ➡ output: {:?}", output);
+    let contents = to_robots_string(
+        args.base_url.as_deref(),
+        args.robots_disallow_list.as_deref().unwrap_or_default(),
+        args.sitemap,
+    );
+    std::fs::write(output.join(ROBOTS_FILE_NAME_AS_STR), contents)
+    .map_or_else(
+        |err| Err(Error::IO(err)),
+        Ok
+    )
+}
+
+/// Write the redirects, i.e. a redirect page for each old URL,
+/// and the redirects file, if wanted, in the output directory.
+///
+/// A redirect never replaces a rendered page.
+fn write_redirects(
+    args: &Args,
+    site_model: &SiteModel,
+    output: &Path,
+    extension: &Path,
+) -> Result<(), Error> {
+    trace!("write_redirects ➡ output: {:?}, extension: {:?}", output, extension);
+    let mut redirects: List<SiteRedirect> = List::new();
+    for redirect in site_model.redirects(args.redirect_map.as_ref()) {
+        let Some(path) = redirect.output_path(extension) else {
+            warn!("write_redirects ➡ skip redirect outside the output directory: {:?}", redirect.from);
+            continue
+        };
+        let output_entry = output.join(path);
+        if site_model.pages.iter().chain(site_model.listings.iter()).any(|x| x.output == output_entry) {
+            warn!("write_redirects ➡ skip redirect that would replace a page: {:?}", redirect.from);
+            continue
+        }
+        if let Some(parent) = output_entry.parent() {
+            std::fs::create_dir_all(parent)
+            .map_or_else(
+                |err| Err(Error::IO(err)),
+                Ok
+            )?;
+        }
+        std::fs::write(&output_entry, redirect.to_html_string(args.base_url.as_deref()))
+        .map_or_else(
+            |err| Err(Error::IO(err)),
+            Ok
+        )?;
+        redirects.push(redirect);
+    }
+    if args.redirects_file {
+        std::fs::write(output.join(REDIRECTS_FILE_NAME_AS_STR), to_redirects_string(&redirects))
+        .map_or_else(
+            |err| Err(Error::IO(err)),
+            Ok
+        )?;
+    }
     Ok(())
 }
 
+/// Write the search index of the listed pages, in the output directory.
+fn write_search_index(
+    args: &Args,
+    site_model: &SiteModel,
+    output: &Path,
+) -> Result<(), Error> {
+    trace!("write_search_index ➡ output: {:?}", output);
+    let fields: List<String> = match &args.search_field_list {
+        Some(x) => x.to_owned(),
+        None => SEARCH_FIELDS_DEFAULT.iter().map(|x| String::from(*x)).collect(),
+    };
+    let contents = site_model.search(args.search_section_list.as_deref(), &fields)
+        .to_json_string(args.search_inverted_index);
+    std::fs::write(output.join(SEARCH_INDEX_FILE_NAME_AS_STR), contents)
+    .map_or_else(
+        |err| Err(Error::IO(err)),
+        Ok
+    )
+}
+
 fn vet_input(
     input: &PathBuf
 ) -> Result<(), Error>  {
@@ -97,6 +425,34 @@ fn vet_output(
     Ok(())    
 }
 
+/// Each page and listing must have its own output path, because a later
+/// one would replace an earlier one, such as two pages with one permalink.
+///
+/// A listing has no input, so the error names its relative path instead.
+fn vet_site_model_outputs(
+    site_model: &SiteModel
+) -> Result<(), Error>  {
+    let origin = |page: &SitePage| match page.input.as_os_str().is_empty() {
+        true => page.path.to_owned(),
+        false => page.input.to_owned(),
+    };
+    let mut outputs: Map<&Path, &SitePage> = Map::new();
+    for page in site_model.pages.iter().chain(site_model.listings.iter()) {
+        if let Some(other) = outputs.insert(&page.output, page) {
+            return Err(Error::OutputMustBeUnique { output: page.output.to_owned(), inputs: [origin(other), origin(page)] })
+        }
+    }
+    Ok(())
+}
+
+/// The sitemap protocol requires absolute URLs, so a sitemap needs a base URL.
+fn vet_sitemap(
+    args: &Args
+) -> Result<(), Error>  {
+    if args.sitemap && args.base_url.is_none() { return Err(Error::SitemapMustHaveBaseUrl) }
+    Ok(())
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
 
@@ -120,12 +476,38 @@ pub enum Error {
         output: PathBuf
     },
 
+    #[error("OutputMustBeUnique ➡ output: {output:?}, inputs: {inputs:?}")]
+    OutputMustBeUnique {
+        output: PathBuf,
+        inputs: [PathBuf; 2],
+    },
+
+    #[error("SitemapMustHaveBaseUrl ➡ the option --sitemap needs the option --base-url")]
+    SitemapMustHaveBaseUrl,
+
     #[error("IO ➡ {0:?}")]
     IO(std::io::Error),
 
     #[error("CookFile ➡ {0:?}")]
     CookFile(crate::cook_file::Error),
 
+    #[error("DefaultsDeclaration ➡ {0:?}")]
+    DefaultsDeclaration(crate::defaults::defaults_declaration::Error),
+
+    #[error("SchemaDeclaration ➡ {0:?}")]
+    SchemaDeclaration(crate::schema::schema_declaration::Error),
+
+    #[error("Schema ➡ violations: {violations:?}")]
+    Schema {
+        violations: List<SchemaViolation>,
+    },
+
+    #[error("Feed ➡ {0:?}")]
+    Feed(quick_xml::Error),
+
+    #[error("Sitemap ➡ {0:?}")]
+    Sitemap(quick_xml::Error),
+
     #[error("WalkDir ➡ {0:?}")]
     WalkDir(walkdir::Error),
 
@@ -142,9 +524,11 @@ pub enum Error {
 mod tests {
     use super::*;
     use assertables::*;
+    use indoc::indoc;
     use once_cell::sync::Lazy;
     use crate::f::remove_dir_if_exists::*;
     use crate::f::remove_file_if_exists::*;
+    use crate::testing::TmpOutputDir;
     use crate::templater::templater_with_handlebars::TemplaterWithHandlebars;
 
     pub static DIR: Lazy<PathBuf> = Lazy::new(||
@@ -189,7 +573,7 @@ mod tests {
         output_subdirs.clone().into_iter().for_each(|dir: PathBuf| assert_ok!(remove_dir_if_exists(&dir), format!("dir: {:?}", &dir)));
 
         // When
-        let result = cook_dir(&args, templater, &input, &output);
+        let result = cook_dir(&args, templater, None, &input, &output);
 
         // Then        
         assert_ok!(result);
@@ -234,7 +618,7 @@ mod tests {
         //output_subdirs.clone().into_iter().for_each(|dir: PathBuf| assert_ok!(remove_dir_if_exists(&dir), format!("dir: {:?}", &dir)));
 
         // When
-        let result = cook_dir(&args, templater, &input, &output);
+        let result = cook_dir(&args, templater, None, &input, &output);
 
         // Then        
         assert_ok!(result);
@@ -244,5 +628,545 @@ mod tests {
     }
 
 
-}
+    #[test]
+    fn test_schema() {
+        let args = Args::default();
+        let templater: Option<&TemplaterWithHandlebars<'_>> = None;
+        let input = DIR.join("test_schema").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_schema");
+
+        // When
+        let result = cook_dir(&args, templater, None, &input, &output);
+
+        // Then
+        match result {
+            Err(Error::Schema { violations }) => {
+                let inputs: List<PathBuf> = violations.iter().map(|violation| match violation {
+                    SchemaViolation::Missing { input, .. } => input.to_owned(),
+                    SchemaViolation::Mismatch { input, .. } => input.to_owned(),
+                }).collect();
+                assert_eq!(inputs, vec![
+                    input.join("posts").join("charlie.md"),
+                    input.join("posts").join("subdir").join("delta.md"),
+                    input.join("posts").join("titled").join("echo.md"),
+                ]);
+            },
+            _ => panic!("result: {:?}", result),
+        }
+        assert!(output.join("alfa.html").is_file());
+        assert!(output.join("posts").join("bravo.html").is_file());
+        assert!(!output.join("posts").join("_schema.html").exists());
+        assert!(!output.join("posts").join("charlie.html").exists());
+        assert!(!output.join("posts").join("titled").join("echo.html").exists());
+        assert!(output.join("posts").join("titled").join("foxtrot.html").is_file());
+    }
+
+    #[test]
+    fn test_defaults() {
+        let args = Args::default();
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{author}} {{feed.enabled}} {{feed.limit}}").expect("register");
+        let input = DIR.join("test_defaults").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_defaults");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("alfa.html"), "  ");
+        assert_eq!(output.read("posts/bravo.html"), "Alice true 10");
+        assert_eq!(output.read("posts/subdir/charlie.html"), "Bob true 20");
+        assert!(!output.join("posts").join("_defaults.html").exists());
+    }
+
+    #[test]
+    fn test_site() {
+        let args = Args::default();
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{title}}:{{#each site.pages}} {{title}}={{url}}{{/each}};{{#each site.sections}} {{url}}={{#each pages}}{{title}},{{/each}}{{/each}}").expect("register");
+        let input = DIR.join("test_site").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_site");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let site = " Alfa=/alfa.html Bravo=/posts/bravo.html Charlie=/posts/charlie.html; /=Alfa, /posts/=Bravo,Charlie,";
+        assert_eq!(output.read("alfa.html"), format!("Alfa:{}", site));
+        assert_eq!(output.read("posts/charlie.html"), format!("Charlie:{}", site));
+    }
+
+    #[test]
+    fn test_sections() {
+        let args = Args::default();
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{title}}{{#if section}}:{{#each section.pages}} {{title}}={{url}}{{/each}}{{/if}}").expect("register");
+        let input = DIR.join("test_sections").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_sections");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("posts/index.html"), "Blog: Bravo=/posts/bravo.html Charlie=/posts/alfa.html");
+        assert_eq!(output.read("posts/alfa.html"), "Charlie");
+        assert!(!output.join("posts").join("_index.html").exists());
+    }
+
+    #[test]
+    fn test_taxonomies() {
+        let args = Args {
+            taxonomy_list: Some(vec![String::from("tags")]),
+            taxonomy_template: Some(String::from("taxonomy.html")),
+            term_template: Some(String::from("term.html")),
+            ..Args::default()
+        };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("taxonomy.html", "{{title}}:{{#each taxonomy.terms}} {{name}}={{url}}{{/each}}").expect("register");
+        templater.register_template_via_name_and_content("term.html", "{{title}}:{{#each term.pages}} {{title}}{{/each}}").expect("register");
+        templater.register_template_via_name_and_content("zulu.html", "{{title}}:{{#each taxonomies.tags}} {{name}}={{url}}{{/each}}").expect("register");
+        let input = DIR.join("test_taxonomies").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_taxonomies");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("alfa.html"), "Alfa: Rust=/tags/rust/ web=/tags/web/");
+        assert_eq!(output.read("bravo.html"), "Bravo: Rust=/tags/rust/");
+        assert_eq!(output.read("tags/index.html"), "Tags: Rust=/tags/rust/ web=/tags/web/");
+        assert_eq!(output.read("tags/rust/index.html"), "Rust: Bravo Alfa");
+        assert_eq!(output.read("tags/web/index.html"), "web: Alfa");
+    }
+
+    #[test]
+    fn test_pagination() {
+        let args = Args::default();
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{title}}{{#if paginator}} {{paginator.current}}/{{paginator.total}}:{{#each paginator.items}} {{title}}{{/each}} prev={{paginator.prev_url}} next={{paginator.next_url}}{{/if}}").expect("register");
+        let input = DIR.join("test_pagination").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_pagination");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("posts/index.html"), "Blog 1/2: Alfa Bravo prev= next=/posts/page/2/");
+        assert_eq!(output.read("posts/page/2/index.html"), "Blog 2/2: Charlie prev=/posts/ next=");
+        assert_eq!(output.read("posts/alfa.html"), "Alfa");
+    }
+
+    #[test]
+    fn test_feeds() {
+        let args = Args {
+            base_url: Some(String::from("https://example.com")),
+            feeds: true,
+            ..Args::default()
+        };
+        let input = DIR.join("test_feeds").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_feeds");
+
+        // When
+        let result = cook_dir::<TemplaterWithHandlebars>(&args, None, None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let atom = output.read("feed.xml");
+        assert_contains!(atom, "<title>Feed</title>");
+        assert_contains!(atom, "<id>https://example.com/posts/bravo.html</id>");
+        assert_not_contains!(atom, "about.html");
+        assert!(atom.find("bravo.html") < atom.find("alfa.html"));
+        assert_contains!(output.read("rss.xml"), "<link>https://example.com/posts/alfa.html</link>");
+        assert_contains!(output.read("feed.json"), r#""feed_url": "https://example.com/feed.json""#);
+        assert_contains!(output.read("posts/feed.xml"), "<title>Blog</title>");
+        assert!(output.join("posts").join("rss.xml").exists());
+        assert!(output.join("posts").join("feed.json").exists());
+    }
+
+    #[test]
+    fn test_sitemap_and_robots() {
+        let args = Args {
+            base_url: Some(String::from("https://example.com")),
+            robots: true,
+            robots_disallow_list: Some(vec![String::from("/drafts/")]),
+            sitemap: true,
+            ..Args::default()
+        };
+        let input = DIR.join("test_feeds").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_sitemap_and_robots");
+
+        // When
+        let result = cook_dir::<TemplaterWithHandlebars>(&args, None, None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let sitemap = output.read("sitemap.xml");
+        assert_contains!(sitemap, "<loc>https://example.com/about.html</loc>");
+        assert_contains!(sitemap, "<loc>https://example.com/posts/</loc>");
+        assert_contains!(sitemap, "<loc>https://example.com/posts/alfa.html</loc>\n    <lastmod>2026-01-02T00:00:00Z</lastmod>");
+        assert_eq!(output.read("robots.txt"), "User-agent: *\nDisallow: /drafts/\n\nSitemap: https://example.com/sitemap.xml\n");
+
+        // When there's no base URL
+        let args = Args { base_url: None, ..args };
+        let output = TmpOutputDir::new("cook_dir_test_sitemap_and_robots_x_no_base_url");
+        let result = cook_dir::<TemplaterWithHandlebars>(&args, None, None, &input, &output);
+
+        // Then
+        assert!(matches!(result, Err(Error::SitemapMustHaveBaseUrl)), "result: {:?}", result);
+        assert!(!output.join("sitemap.xml").exists());
+        assert!(!output.join("robots.txt").exists());
+    }
+
+    #[test]
+    fn test_search_index() {
+        let args = Args {
+            search: true,
+            search_inverted_index: true,
+            search_section_list: Some(vec![String::from("posts")]),
+            ..Args::default()
+        };
+        let input = DIR.join("test_feeds").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_search_index");
+
+        // When
+        let result = cook_dir::<TemplaterWithHandlebars>(&args, None, None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let s = output.read("search-index.json");
+        let value: serde_json::Value = serde_json::from_str(&s).expect("json");
+        let pages = value["pages"].as_array().expect("pages");
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0]["url"], "/posts/alfa.html");
+        assert_eq!(pages[0]["title"], "Alfa");
+        assert_eq!(pages[0]["headings"], serde_json::json!(["Alfa"]));
+        assert_eq!(pages[0]["text"], "alfa alfa more");
+        assert_eq!(value["index"]["alfa"], serde_json::json!([0]));
+        assert_eq!(value["index"]["more"], serde_json::json!([0]));
+        assert_eq!(value["index"].get("about"), None);
+    }
+
+    #[test]
+    fn test_drafts() {
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{title}}:{{#each site.pages}} {{title}}{{/each}}").expect("register");
+        let input = DIR.join("test_drafts").join("input");
+        let base_url = Some(String::from("https://example.com"));
+        for (args, name, expect) in [
+            (Args { base_url: base_url.clone(), sitemap: true, ..Args::default() }, "cook_dir_test_drafts", "Alfa: Alfa"),
+            (Args { base_url: base_url.clone(), sitemap: true, drafts: true, future: true, ..Args::default() }, "cook_dir_test_drafts_x_drafts_and_future", "Alfa: Alfa Bravo Charlie"),
+        ] {
+            let output = TmpOutputDir::new(name);
+
+            // When
+            let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+            // Then
+            assert_ok!(result);
+            assert_eq!(output.read("posts/alfa.html"), expect);
+            assert_eq!(output.join("posts").join("bravo.html").exists(), args.drafts);
+            assert_eq!(output.join("posts").join("charlie.html").exists(), args.future);
+            assert!(!output.join("posts").join("delta.html").exists());
+            assert_not_contains!(output.read("sitemap.xml"), "delta.html");
+        }
+    }
+
+    #[test]
+    fn test_redirects() {
+        let args = Args {
+            base_url: Some(String::from("https://example.com")),
+            redirect_map: Some(map!(String::from("/blog/") => String::from("/posts/"))),
+            redirects_file: true,
+            ..Args::default()
+        };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{title}}").expect("register");
+        let input = DIR.join("test_redirects").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_redirects");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let alias = output.read("old/alfa/index.html");
+        assert_contains!(alias, r#"<link rel="canonical" href="https://example.com/posts/alfa.html">"#);
+        assert_contains!(alias, r#"<meta http-equiv="refresh" content="0; url=https://example.com/posts/alfa.html">"#);
+        assert_contains!(output.read("old-alfa.html"), "https://example.com/posts/alfa.html");
+        assert_contains!(output.read("blog/index.html"), "https://example.com/posts/");
+        assert_eq!(output.read("posts/bravo.html"), "Bravo");
+        assert!(!crate::testing::TMP_DIR.join("escape").exists());
+        assert_eq!(output.read("_redirects"), indoc!{"
+            /old/alfa/ /posts/alfa.html 301
+            /old-alfa.html /posts/alfa.html 301
+            /blog/ /posts/ 301
+        "});
+    }
+
+    #[test]
+    fn test_redirects_x_extension() {
+        let args = Args {
+            output_file_name_extension: Some(PathBuf::from("htm")),
+            redirect_map: Some(map!(
+                String::from("/blog/") => String::from("/posts/"),
+                String::from("/posts/bravo.htm") => String::from("/elsewhere/")
+            )),
+            ..Args::default()
+        };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{title}}").expect("register");
+        let input = DIR.join("test_redirects").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_redirects_x_extension");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_contains!(output.read("old/alfa/index.htm"), "/posts/alfa.htm");
+        assert_contains!(output.read("blog/index.htm"), "/posts/");
+        assert_eq!(output.read("posts/bravo.htm"), "Bravo");
+        assert!(!output.join("blog").join("index.html").exists());
+    }
+
+    #[test]
+    fn test_languages() {
+        let args = Args { language_list: Some(vec![String::from("en"), String::from("fr")]), ..Args::default() };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        let (name, content) = (templater.template_name_default().to_string(), templater.template_content_default().to_string());
+        templater.register_template_via_name_and_content(&name, &content).expect("register");
+        let input = DIR.join("test_languages").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_languages");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let about = output.read("about.html");
+        assert_contains!(about, r#"<html lang="en">"#);
+        assert_contains!(about, r#"<link rel="alternate" hreflang="fr" href="/fr/about.html">"#);
+        let about_fr = output.read("fr/about.html");
+        assert_contains!(about_fr, r#"<html lang="fr">"#);
+        assert_contains!(about_fr, r#"<link rel="alternate" hreflang="en" href="/about.html">"#);
+        assert_contains!(output.read("fr/posts/index.html"), "<title>Articles</title>");
+        assert_contains!(output.read("fr/posts/alfa.html"), r#"<a href="../about.html">About</a>"#);
+        assert_contains!(output.read("posts/bravo.html"), r#"<html lang="en">"#);
+        assert!(!output.join("about.fr.html").exists());
+    }
+
+    #[test]
+    fn test_languages_x_feeds() {
+        let args = Args {
+            base_url: Some(String::from("https://example.com")),
+            feeds: true,
+            language_list: Some(vec![String::from("en"), String::from("fr")]),
+            ..Args::default()
+        };
+        let input = DIR.join("test_languages_x_feeds").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_languages_x_feeds");
+
+        // When
+        let result = cook_dir::<TemplaterWithHandlebars>(&args, None, None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_contains!(output.read("fr/posts/feed.xml"), "<id>https://example.com/fr/posts/alfa.html</id>");
+        assert!(output.join("fr").join("posts").join("alfa.html").is_file());
+    }
+
+    #[test]
+    fn test_permalinks() {
+        let args = Args { permalink_map: Some(map!(String::from("posts") => String::from("/blog/:year/:month/:slug/"))), ..Args::default() };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{url}}\n{{{content}}}").expect("register");
+        let input = DIR.join("test_permalinks").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_permalinks");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let alfa = output.read("blog/2026/10/alfa/index.html");
+        assert_starts_with!(alfa, "/blog/2026/10/alfa/\n");
+        assert_contains!(alfa, r#"<a href="/about.html">About</a>"#);
+        assert_starts_with!(output.read("bravo/index.html"), "/bravo/\n");
+        assert_starts_with!(output.read("posts/index.html"), "/posts/\n");
+        let about = output.read("about.html");
+        assert_contains!(about, r#"<a href="/blog/2026/10/alfa/#top">Alfa</a>"#);
+        assert_contains!(about, r#"<a href="/posts/">Posts</a>"#);
+        assert_contains!(about, r#"<a href="/bravo/">Bravo</a>"#);
+        assert!(!output.join("posts").join("alfa.html").exists());
+    }
+
+    #[test]
+    fn test_output_file_name_extension() {
+        let args = Args { output_file_name_extension: Some(PathBuf::from("htm")), ..Args::default() };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{url}}\n{{{content}}}").expect("register");
+        let input = DIR.join("test_output_file_name_extension").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_output_file_name_extension");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let alfa = output.read("alfa.htm");
+        assert_starts_with!(alfa, "/alfa.htm\n");
+        assert_contains!(alfa, r#"<a href="posts/bravo.htm">Bravo</a>"#);
+        assert_contains!(alfa, r#"<img src="img/logo.png" alt="Logo" />"#);
+        assert_contains!(output.read("posts/bravo.htm"), r#"<a href="../alfa.htm">Alfa</a>"#);
+    }
+
+    #[test]
+    fn test_data_dir_inside_input() {
+        let input = DIR.join("test_data_dir_inside_input").join("input");
+        let args = Args { data_dir: Some(input.join("data")), ..Args::default() };
+        let output = TmpOutputDir::new("cook_dir_test_data_dir_inside_input");
+
+        // When
+        let result = cook_dir::<TemplaterWithHandlebars>(&args, None, None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert!(output.join("alfa.html").exists());
+        assert!(!output.join("data").exists());
+    }
+
+    #[test]
+    fn test_permalinks_x_duplicate() {
+        let args = Args { permalink_map: Some(map!(String::from("posts") => String::from("/blog/:slug/"))), ..Args::default() };
+        let input = DIR.join("test_permalinks_x_duplicate").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_permalinks_x_duplicate");
+
+        // When
+        let result = cook_dir::<TemplaterWithHandlebars>(&args, None, None, &input, &output);
+
+        // Then
+        match result {
+            Err(Error::OutputMustBeUnique { output: x, inputs }) => {
+                assert_eq!(x, output.join("blog").join("hello").join("index.html"));
+                assert_eq!(inputs, [
+                    input.join("posts").join("a").join("hello.md"),
+                    input.join("posts").join("b").join("hello.md"),
+                ]);
+            },
+            _ => panic!("result: {:?}", result),
+        }
+        assert!(!output.join("blog").exists());
+    }
+
+    #[test]
+    fn test_not_found() {
+        let args = Args { base_url: Some(String::from("https://example.com")), sitemap: true, ..Args::default() };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{title}}:{{#each site.pages}} {{url}}{{/each}}\n{{{content}}}").expect("register");
+        let input = DIR.join("test_not_found").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_not_found");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        let not_found = output.read("404.html");
+        assert_starts_with!(not_found, "Not found: / /posts/alfa.html\n");
+        assert_contains!(not_found, r#"<a href="/index.html">home</a>"#);
+        assert_contains!(not_found, r#"<a href="/posts/">posts</a>"#);
+        assert_contains!(not_found, r#"<img src="/img/logo.png" alt="logo" />"#);
+        assert_not_contains!(output.read("sitemap.xml"), "404.html");
+    }
 
+    #[test]
+    fn test_not_found_x_template() {
+        let args = Args { not_found_template: Some(String::from("404.html")), ..Args::default() };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("404.html", "{{title}} <a href=\"posts/\">posts</a>").expect("register");
+        templater.register_template_via_name_and_content("template.html", "{{title}}").expect("register");
+        let input = DIR.join("test_sections").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_not_found_x_template");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("404.html"), r#"Page not found <a href="/posts/">posts</a>"#);
+        assert_eq!(output.read("posts/alfa.html"), "Charlie");
+    }
+
+    #[test]
+    fn test_archive() {
+        let args = Args {
+            archive: true,
+            archive_template: Some(String::from("archive.html")),
+            archive_month_template: Some(String::from("month.html")),
+            ..Args::default()
+        };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("archive.html", "{{title}}:{{#each archive.years}} {{year}}={{count}}{{#each months}} {{title}}={{url}}{{/each}}{{/each}}").expect("register");
+        templater.register_template_via_name_and_content("month.html", "{{title}}:{{#each month.pages}} {{title}}{{/each}}").expect("register");
+        templater.register_template_via_name_and_content("template.html", "{{title}}{{#if year}}:{{#each year.pages}} {{title}}{{/each}}{{/if}}").expect("register");
+        let input = DIR.join("test_feeds").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_archive");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("archive/index.html"), "Archive: 2026=2 February 2026=/archive/2026/02/ January 2026=/archive/2026/01/");
+        assert_eq!(output.read("archive/2026/index.html"), "2026: Bravo Alfa");
+        assert_eq!(output.read("archive/2026/01/index.html"), "January 2026: Alfa");
+        assert_eq!(output.read("about.html"), "About");
+    }
+
+    #[test]
+    fn test_prev_next_and_breadcrumbs() {
+        let args = Args::default();
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("template.html", "{{#each breadcrumbs}}{{title}}={{url}} {{/each}}| {{title}}{{#if prev}} prev={{prev.url}}{{/if}}{{#if next}} next={{next.url}}{{/if}}").expect("register");
+        let input = DIR.join("test_sections").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_prev_next_and_breadcrumbs");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("posts/index.html"), "Home=/ | Blog");
+        assert_eq!(output.read("posts/bravo.html"), "Home=/ Blog=/posts/ | Bravo next=/posts/alfa.html");
+        assert_eq!(output.read("posts/alfa.html"), "Home=/ Blog=/posts/ | Charlie prev=/posts/bravo.html");
+    }
+
+    #[test]
+    fn test_related() {
+        let args = Args {
+            taxonomy_list: Some(vec![String::from("tags")]),
+            taxonomy_template: Some(String::from("listing.html")),
+            term_template: Some(String::from("listing.html")),
+            ..Args::default()
+        };
+        let mut templater: TemplaterWithHandlebars<'_> = TemplaterWithHandlebars::new();
+        templater.register_template_via_name_and_content("listing.html", "{{title}}").expect("register");
+        templater.register_template_via_name_and_content("zulu.html", "{{title}}:{{#each related}} {{title}}={{url}}{{/each}}").expect("register");
+        let input = DIR.join("test_taxonomies").join("input");
+        let output = TmpOutputDir::new("cook_dir_test_related");
+
+        // When
+        let result = cook_dir(&args, Some(&templater), None, &input, &output);
+
+        // Then
+        assert_ok!(result);
+        assert_eq!(output.read("alfa.html"), "Alfa: Bravo=/bravo.html");
+        assert_eq!(output.read("bravo.html"), "Bravo: Alfa=/alfa.html");
+    }
+
+}
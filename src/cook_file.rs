@@ -1,36 +1,183 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use crate::app::args::Args;
+use crate::types::{list::*, map::*, set::*};
+use crate::defaults::defaults_declaration::DefaultsDeclaration;
+use crate::schema::schema_declaration::SchemaDeclaration;
+use crate::schema::schema_violation::SchemaViolation;
 use crate::state::state_trait::StateTrait;
 use crate::templater::templater_trait::TemplaterTrait;
 use crate::f::from_markdown_str_into_html_string::*;
-use crate::f::from_html_str_into_headline_str::*;
-use crate::f::from_html_str_into_paragraph_str::*;
+use crate::f::from_relative_path_into_url_string::*;
+use crate::markdown::markdown_document::MarkdownDocument;
+use crate::markdown::markdown_heading::MarkdownHeading;
+use crate::markdown::markdown_toc::*;
+use crate::f::from_html_str_into_summary_str::*;
+use crate::f::from_html_str_into_text_string::*;
+use crate::f::from_str_into_slug_string::*;
+use crate::f::from_str_into_title_string::*;
+use crate::f::from_str_into_truncated_string::*;
+use crate::state::state_value::{StateValue, StateValueDeepMerge};
+use crate::site::site_model::SiteModel;
+use crate::site::site_page::SitePage;
+use crate::site::site_permalink::*;
+use unicode_segmentation::UnicodeSegmentation;
 
+/// Reading speed for the `reading_time` variable, in words per minute.
+pub const READING_WORDS_PER_MINUTE: usize = 200;
+
+/// Description length for the `description` variable, in characters.
+pub const DESCRIPTION_LENGTH_DEFAULT: usize = 160;
+
+/// Cook one file, as a site of one page.
 pub (crate) fn cook_file<T: TemplaterTrait> (
     args: &Args,
     templater: Option<&T>,
+    schema: Option<&SchemaDeclaration>,
+    defaults: Option<&DefaultsDeclaration>,
     input: &PathBuf,
     output: &PathBuf,
+    url: &str,
 ) -> Result<(), Error> {
     trace!("Cook file.");
-    debug!("cook_file ➡ args: {:?}, templater: {:?}, input: {:?}, output: {:?}", args, templater, input, output);
+    debug!("cook_file ➡ args: {:?}, templater: {:?}, schema: {:?}, defaults: {:?}, input: {:?}, output: {:?}, url: {:?}", args, templater, schema, defaults, input, output, url);
+    let path = input.file_name().map(Path::new).unwrap_or(input);
+    let page = prepare_page(args, schema, defaults, input, output, path, url, None)?;
+    render_site_model(args, templater, &mut SiteModel { pages: vec![page], ..Default::default() })
+}
+
+/// Prepare a page, which is the first phase of cooking a file.
+///
+/// This reads the input, parses its front matter and Markdown,
+/// applies the defaults, sets the computed variables, and vets
+/// the state via the schema, without rendering nor writing.
+///
+/// The permalink pattern, if any, sets the `url` variable, unless
+/// the front matter sets it; see `site_permalink.rs`.
+#[allow(clippy::too_many_arguments)]
+pub (crate) fn prepare_page(
+    args: &Args,
+    schema: Option<&SchemaDeclaration>,
+    defaults: Option<&DefaultsDeclaration>,
+    input: &PathBuf,
+    output: &PathBuf,
+    path: &Path,
+    url: &str,
+    permalink: Option<&str>,
+) -> Result<SitePage, Error> {
+    trace!("Prepare page.");
+    debug!("prepare_page ➡ schema: {:?}, defaults: {:?}, input: {:?}, output: {:?}, path: {:?}, url: {:?}, permalink: {:?}", schema, defaults, input, output, path, url, permalink);
     vet_input(input)?;
     vet_output(output)?;
     let mix_text = read_input_into_mix_text(input)?;
     let (content_text, state) = parse_mix_to_content_text_and_state(mix_text)?;
-    let input_html_text = convert_from_markdown_str_into_html_string(&content_text);
+    let state = apply_defaults(defaults, state);
+    // Vet the front matter and defaults, before any computed variables, such as `title`.
+    vet_state(schema, input, state.as_ref())?;
+    let has_url = state.contains_key("url");
+    let document = convert_from_markdown_str_into_markdown_document(&content_text, args.remove_title);
+    let state = insert_state_variables(state, &document, input, output, path, url, args.description_length.unwrap_or(DESCRIPTION_LENGTH_DEFAULT));
+    let state = insert_state_toc(state, &document.headings, args.toc_depth.unwrap_or(TOC_DEPTH_DEFAULT));
+    let state = match (has_url, permalink) {
+        (false, Some(pattern)) => insert_state_permalink(state, pattern),
+        _ => state,
+    };
+    Ok(SitePage {
+        input: input.to_owned(),
+        output: output.to_owned(),
+        path: path.to_path_buf(),
+        html: document.html,
+        text: document.text,
+        headings: document.headings.into_iter().map(|x| x.text).collect(),
+        template_name: None,
+        items: None,
+        state,
+    })
+}
+
+/// Render every page of a site model, which is the second phase of cooking.
+///
+/// This sets each page's variables that depend on the whole site,
+/// such as `site` and `section`, and paginates each listing, then
+/// renders each page and listing.
+///
+/// A template name that a page or listing chooses is reserved for it,
+/// so the default page template is never a taxonomy template, etc.
+///
+/// The site model is kept, with its listings, for any site-wide outputs
+/// that come after rendering, such as a sitemap.
+pub (crate) fn render_site_model<T: TemplaterTrait> (
+    args: &Args,
+    templater: Option<&T>,
+    site_model: &mut SiteModel,
+) -> Result<(), Error> {
+    trace!("Render site model.");
+    let extension = match &args.output_file_name_extension {
+        Some(x) => x,
+        None => &crate::app::args::OUTPUT_FILE_NAME_EXTENSION_AS_PATH_BUF,
+    };
+    let site = site_model.to_state_value();
+    let permalinks = site_model.permalinks(extension);
+    site_model.insert_page_variables();
+    site_model.paginate(args.paginate_by);
+    let reserved: Set<String> = site_model.pages.iter().chain(site_model.listings.iter())
+        .filter_map(|x| x.template_name.to_owned())
+        .collect();
+    for page in site_model.pages.iter().chain(site_model.listings.iter()) {
+        render_page(templater, page, &site, &reserved, &permalinks, extension)?;
+    }
+    Ok(())
+}
+
+/// Render a page, which is the second phase of cooking a file.
+///
+/// This renders via the templater if any, with the `site` variable as a
+/// separate layer over the page state, or else uses the content, then
+/// rewrites and writes the output.
+///
+/// Every page shares the one `site` value, rather than a copy in its state.
+///
+/// The permalinks rewrite each Markdown link to a page whose URL isn't
+/// its input path, such as via a permalink pattern; see `SiteModel::permalinks`.
+fn render_page<T: TemplaterTrait> (
+    templater: Option<&T>,
+    page: &SitePage,
+    site: &StateValue,
+    reserved: &Set<String>,
+    permalinks: &Map<String, String>,
+    extension: &Path,
+) -> Result<(), Error> {
+    trace!("Render page.");
+    debug!("render_page ➡ templater: {:?}, input: {:?}, output: {:?}", templater, page.input, page.output);
     let output_html_text = match templater {
         Some(templater) => {
-            let state = insert_state_variables(state, &input_html_text);
-            let template_name = get_template_name(templater)?;    
-            render(templater, &template_name, &state)?
+            let template_name = match &page.template_name {
+                Some(x) => vet_template_name(templater, x)?,
+                None => get_template_name(templater, reserved)?,
+            };
+            render(templater, template_name, &page.state, site)
         },
         None => {
-            input_html_text
+            Ok(page.html.to_owned())
         }
     };
-    let output_html_text = crate::rewriting::lol::rewrite(&output_html_text);
-    write_output(output, &output_html_text)?;
+    // The not found page is served from any URL depth, so its links must be root-relative.
+    let output_html_text = match page.is_not_found() {
+        true => {
+            let url = page.state.get_value("url").map(|x| x.to_plain_string()).unwrap_or_default();
+            let dir_url = url.rfind('/').map_or("/", |i| &url[..=i]);
+            crate::rewriting::lol::rewrite_with_root_links(&output_html_text?, dir_url, permalinks, extension)
+        },
+        // A page with a permalink can be at any URL depth, so its links must be root-relative, from its input directory.
+        false => {
+            let dir_url = from_relative_path_into_url_string(&page.dir().join(INDEX_FILE_NAME_AS_STR));
+            match permalinks.contains_key(&from_relative_path_into_url_string(&page.path)) {
+                true => crate::rewriting::lol::rewrite_with_root_links(&output_html_text?, &dir_url, permalinks, extension),
+                false => crate::rewriting::lol::rewrite(&output_html_text?, &dir_url, permalinks, extension),
+            }
+        },
+    };
+    create_output_dir(&page.output)?;
+    write_output(&page.output, &output_html_text)?;
     Ok(())
 }
 
@@ -73,6 +220,7 @@ fn parse_mix_to_content_text_and_state(
     )
 }
 
+#[allow(dead_code)]
 fn convert_from_markdown_str_into_html_string(
     markdown_str: &str
 ) -> String {
@@ -81,14 +229,50 @@ fn convert_from_markdown_str_into_html_string(
     from_markdown_str_into_html_string(&markdown_str)
 }
 
+fn convert_from_markdown_str_into_markdown_document(
+    markdown_str: &str,
+    remove_title: bool,
+) -> MarkdownDocument {
+    trace!("Convert from markdown text into markdown document.");
+    debug!("markdown_str: {:?}, remove_title: {:?}", markdown_str, remove_title);
+    MarkdownDocument::from_markdown_str(markdown_str, remove_title)
+}
+
+/// Apply the defaults, if any, to the state.
+///
+/// This always returns a typed state, even without defaults, because some
+/// front matter flavors hold only strings, yet variables such as `site`
+/// and `toc` need lists and maps for templates to iterate.
+fn apply_defaults(
+    defaults: Option<&DefaultsDeclaration>,
+    state: Box<dyn StateTrait>,
+) -> Box<dyn StateTrait> {
+    trace!("Apply defaults to state.");
+    match defaults {
+        Some(defaults) => defaults.apply(state.as_ref()),
+        None => DefaultsDeclaration::default().apply(state.as_ref()),
+    }
+}
+
 fn insert_state_variables(
     mut state: Box<dyn StateTrait>, 
-    html: &str
+    document: &MarkdownDocument,
+    input: &Path,
+    output: &Path,
+    path: &Path,
+    url: &str,
+    description_length: usize,
 ) -> Box<dyn StateTrait> {
     trace!("Set state variables as needed.");
+    let html = document.html.as_str();
     state = insert_state_content(state, html);
-    state = insert_state_title(state, html);
-    state = insert_state_description(state, html);
+    state = insert_state_title(state, document.title.as_deref(), path);
+    state = insert_state_summary(state, html, document.paragraph_html.as_deref());
+    state = insert_state_description(state, document.summary_text.as_deref().or(document.paragraph.as_deref()), description_length);
+    state = insert_state_word_count_and_reading_time(state, html);
+    state = insert_state_paths(state, input, output, url);
+    state = insert_state_slug(state, path);
+    state = insert_state_file_modified(state, input);
     state
 }
 
@@ -103,53 +287,195 @@ fn insert_state_content(
     state
 }
 
+/// Insert the `title` variable, which is the plain text of the first H1,
+/// or else a title made from the page file name, without any language code.
 fn insert_state_title(
     mut state: Box<dyn StateTrait>, 
-    html: &str
+    title: Option<&str>,
+    path: &Path,
 ) -> Box<dyn StateTrait> {
     trace!("insert_state_title");
     if !state.contains_key("title") {
-        if let Some(s) = from_html_str_into_headline_str(html) {
-            state.insert(String::from("title"), String::from(s));
+        let title = match title {
+            Some(s) => String::from(s),
+            None => path.file_stem().map(|x| from_str_into_title_string(&x.to_string_lossy())).unwrap_or_default(),
+        };
+        if !title.is_empty() {
+            state.insert(String::from("title"), title);
+        }
+    }
+    state
+}
+
+/// Insert the `summary` variable, which is the HTML before the marker `<!-- more -->`,
+/// or else the first paragraph.
+fn insert_state_summary(
+    mut state: Box<dyn StateTrait>, 
+    html: &str,
+    paragraph_html: Option<&str>,
+) -> Box<dyn StateTrait> {
+    trace!("insert_state_summary");
+    if !state.contains_key("summary") {
+        if let Some(s) = from_html_str_into_summary_str(html).or(paragraph_html) {
+            state.insert(String::from("summary"), String::from(s));
         }
     }
     state
 }
 
+/// Insert the `description` variable, which is the plain text before the
+/// marker `<!-- more -->` without headings, or else the first paragraph as
+/// plain text, both from the Markdown events, truncated on a word boundary
+/// to the description length.
 fn insert_state_description(
     mut state: Box<dyn StateTrait>, 
-    html: &str
+    text: Option<&str>,
+    length: usize,
 ) -> Box<dyn StateTrait> {
     trace!("insert_state_description");
     if !state.contains_key("description") {
-        if let Some(s) = from_html_str_into_paragraph_str(html) {
-            state.insert(String::from("description"), String::from(s));
+        if let Some(text) = text {
+            state.insert(String::from("description"), from_str_into_truncated_string(text, length));
         }
     }
     state
 }
 
-fn get_template_name<T: TemplaterTrait>(
-    templater: &T,
-) -> Result<&str, Error> {
-    //TODO make dynamic; currently this implementation merely returns the first available name
+fn insert_state_word_count_and_reading_time(
+    mut state: Box<dyn StateTrait>, 
+    html: &str
+) -> Box<dyn StateTrait> {
+    trace!("insert_state_word_count_and_reading_time");
+    let word_count = from_html_str_into_text_string(html).unicode_words().count();
+    if !state.contains_key("word_count") {
+        state.insert_value(String::from("word_count"), StateValue::Int(word_count as i64));
+    }
+    if !state.contains_key("reading_time") {
+        let reading_time = word_count.div_ceil(READING_WORDS_PER_MINUTE).max(1);
+        state.insert_value(String::from("reading_time"), StateValue::Int(reading_time as i64));
+    }
+    state
+}
+
+fn insert_state_paths(
+    mut state: Box<dyn StateTrait>, 
+    input: &Path,
+    output: &Path,
+    url: &str,
+) -> Box<dyn StateTrait> {
+    trace!("insert_state_paths");
+    state.contains_key_or_insert(String::from("source_path"), input.to_string_lossy().into());
+    state.contains_key_or_insert(String::from("output_path"), output.to_string_lossy().into());
+    state.contains_key_or_insert(String::from("url"), String::from(url));
+    state
+}
+
+/// Insert the `url` variable from a permalink pattern, such as "/blog/:year/:slug/",
+/// which replaces the URL from the input path.
+fn insert_state_permalink(
+    mut state: Box<dyn StateTrait>, 
+    pattern: &str,
+) -> Box<dyn StateTrait> {
+    trace!("insert_state_permalink");
+    let url = from_pattern_and_state_into_url_string(pattern, state.as_ref());
+    state.insert(String::from("url"), url);
+    state
+}
+
+/// Insert the `slug` variable, which is made from the page file name,
+/// without any language code.
+fn insert_state_slug(
+    mut state: Box<dyn StateTrait>, 
+    path: &Path,
+) -> Box<dyn StateTrait> {
+    trace!("insert_state_slug");
+    if let Some(file_stem) = path.file_stem() {
+        state.contains_key_or_insert(String::from("slug"), from_str_into_slug_string(&file_stem.to_string_lossy()));
+    }
+    state
+}
+
+fn insert_state_file_modified(
+    mut state: Box<dyn StateTrait>, 
+    input: &Path,
+) -> Box<dyn StateTrait> {
+    trace!("insert_state_file_modified");
+    if let Ok(modified) = std::fs::metadata(input).and_then(|x| x.modified()) {
+        state.contains_key_or_insert(String::from("file_modified"), httpdate::fmt_http_date(modified));
+    }
+    state
+}
+
+fn insert_state_toc(
+    mut state: Box<dyn StateTrait>, 
+    headings: &[MarkdownHeading],
+    depth: u8,
+) -> Box<dyn StateTrait> {
+    trace!("insert_state_toc");
+    let toc = from_headings_into_toc(headings, depth);
+    if !state.contains_key("toc_html") {
+        state.insert(String::from("toc_html"), from_toc_into_html_string(&toc));
+    }
+    if !state.contains_key("toc") {
+        let value = serde_json::to_value(&toc).unwrap_or(serde_json::Value::Null);
+        state.insert_value(String::from("toc"), StateValue::from(value));
+    }
+    state
+}
+
+fn vet_state(
+    schema: Option<&SchemaDeclaration>,
+    input: &PathBuf,
+    state: &dyn StateTrait,
+) -> Result<(), Error> {
+    trace!("Vet state via schema.");
+    if let Some(schema) = schema {
+        let violations = schema.validate(input, &state.to_state_enum());
+        if !violations.is_empty() {
+            return Err(Error::Schema { input: input.to_owned(), violations })
+        }
+    }
+    Ok(())
+}
+
+fn get_template_name<'a, T: TemplaterTrait>(
+    templater: &'a T,
+    reserved: &Set<String>,
+) -> Result<&'a str, Error> {
+    //TODO make dynamic; currently this implementation merely returns the first available name that isn't reserved
     trace!("Get template name.");
-    debug!("templater: {:?}", templater);
+    debug!("templater: {:?}, reserved: {:?}", templater, reserved);
     let template_names = templater.template_names_as_set_str();
-    match template_names.iter().next() {
+    match template_names.iter().find(|&&x| !reserved.contains(x)).or(template_names.iter().next()) {
         Some(&s) => Ok(s),
         None => Ok(templater.template_name_default())
     }
 }
 
+fn vet_template_name<'a, T: TemplaterTrait>(
+    templater: &T,
+    template_name: &'a str,
+) -> Result<&'a str, Error> {
+    trace!("Vet template name.");
+    if templater.contains_template_name(template_name) {
+        Ok(template_name)
+    } else {
+        Err(Error::TemplateNameMustExist { template_name: template_name.to_owned() })
+    }
+}
+
 fn render<T: TemplaterTrait>(
     templater: &T, 
     template_name: &str, 
-    state: &Box<dyn StateTrait>
+    state: &Box<dyn StateTrait>,
+    site: &StateValue,
 ) -> Result<String, Error> {
     trace!("Render via the templater trait and state enum.");
     debug!("templater: {:?}, template_name: {:?}, state: {:?}", templater, template_name, state);
-    templater.render_template_with_state_enum(&template_name, &state.to_state_enum())
+    // The page's own `site` variable, such as from `--set site.title`, is the base under the site.
+    let state_site = state.get_value("site");
+    let site = StateValueDeepMerge { base: state_site.as_ref(), overlay: site };
+    templater.render_template_with_state_enum_and_site(&template_name, &state.to_state_enum(), &site)
     .map_or_else(
         |err| Err(Error::Render { 
             //templater: Box::new(templater), //TODO fix
@@ -160,6 +486,22 @@ fn render<T: TemplaterTrait>(
     )
 }
 
+fn create_output_dir(
+    output: &Path,
+) -> Result<(), Error> {
+    match output.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() && !dir.exists() => {
+            trace!("Create output dir.");
+            std::fs::create_dir_all(dir)
+            .map_or_else(
+                |err| Err(Error::CreateOutputDir { output: output.to_owned(), err }),
+                Ok
+            )
+        },
+        _ => Ok(()),
+    }
+}
+
 fn write_output(
     output: &PathBuf, 
     contents: &str
@@ -207,6 +549,23 @@ pub enum Error {
         err: crate::matter::matter_parser_mutex::Error,
     },
 
+    #[error("Schema ➡ input: {input:?}, violations: {violations:?}")]
+    Schema {
+        input: PathBuf,
+        violations: List<SchemaViolation>,
+    },
+
+    #[error("TemplateNameMustExist ➡ template_name: {template_name:?}")]
+    TemplateNameMustExist {
+        template_name: String,
+    },
+
+    #[error("CreateOutputDir ➡ output: {output:?}, err {err:?}")]
+    CreateOutputDir {
+        output: PathBuf,
+        err: std::io::Error,
+    },
+
     #[error("Render ➡ template_name: {template_name:?}")] //TODO "Render ➡ templater: {templater:?}, template_name: {template_name:?}, err: {err:?}"
     Render {
         //templater: Box<dyn TemplaterTrait>, //TODO fix
@@ -246,7 +605,7 @@ mod tests {
         let output = dir.join("example.html");
         let expect = dir.join("example.html=expect.html");
         assert_ok!(remove_file_if_exists(&output));
-        let result = cook_file(&args, option_templater, &input, &output);
+        let result = cook_file(&args, option_templater, None, None, &input, &output, "/example.html");
         assert_ok!(result);
         assert_fs_read_to_string_eq!(&output, &expect);
     }
@@ -261,11 +620,137 @@ mod tests {
         let output = dir.join("example.html");
         let expect = dir.join("example.html=expect.html");
         assert_ok!(remove_file_if_exists(&output));
-        let result = cook_file(&args, Some(&templater), &input, &output);
+        let result = cook_file(&args, Some(&templater), None, None, &input, &output, "/example.html");
         assert_ok!(result);
         assert_fs_read_to_string_eq!(&output, &expect);
     }
 
+    #[test]
+    fn test_with_schema_x_ok() {
+        let dir = DIR.join("test_with_schema");
+        let args = Args::default();
+        let option_templater: Option<&TemplaterWithHandlebars<'_>> = None;
+        let schema = SchemaDeclaration::load_from_dir(&dir).expect("load").expect("some");
+        let input = dir.join("valid.md");
+        let output = dir.join("valid.html");
+        assert_ok!(remove_file_if_exists(&output));
+        let result = cook_file(&args, option_templater, Some(&schema), None, &input, &output, "/valid.html");
+        assert_ok!(result);
+        assert!(output.exists());
+        assert_ok!(remove_file_if_exists(&output));
+    }
+
+    #[test]
+    fn test_with_schema_x_err() {
+        let dir = DIR.join("test_with_schema");
+        let args = Args::default();
+        let option_templater: Option<&TemplaterWithHandlebars<'_>> = None;
+        let schema = SchemaDeclaration::load_from_dir(&dir).expect("load").expect("some");
+        let input = dir.join("invalid.md");
+        let output = dir.join("invalid.html");
+        assert_ok!(remove_file_if_exists(&output));
+        let result = cook_file(&args, option_templater, Some(&schema), None, &input, &output, "/invalid.html");
+        match result {
+            Err(Error::Schema { input: x, violations }) => {
+                assert_eq!(x, input);
+                assert_eq!(violations.len(), 2);
+            },
+            _ => panic!("result: {:?}", result),
+        }
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_insert_state_variables_x_computed() {
+        let input = DIR.join("test_sans_templater").join("example.md");
+        let output = PathBuf::from("posts/example.html");
+        let state: Box<dyn StateTrait> = Box::new(crate::state::state_with_json::StateWithJSON::new());
+        let document = MarkdownDocument::from_markdown_str("# Alfa\nbravo charlie\n", false);
+        let state = super::insert_state_variables(state, &document, &input, &output, &input, "/posts/example.html", DESCRIPTION_LENGTH_DEFAULT);
+        assert_eq!(state.get_value("word_count"), Some(StateValue::Int(3)));
+        assert_eq!(state.get_value("reading_time"), Some(StateValue::Int(1)));
+        assert_eq!(state.get_value("source_path"), Some(StateValue::from(input.to_string_lossy().as_ref())));
+        assert_eq!(state.get_value("output_path"), Some(StateValue::from("posts/example.html")));
+        assert_eq!(state.get_value("url"), Some(StateValue::from("/posts/example.html")));
+        assert_eq!(state.get_value("slug"), Some(StateValue::from("example")));
+        assert!(matches!(state.get_value("file_modified"), Some(StateValue::String(x)) if x.ends_with(" GMT")));
+    }
+
+    #[test]
+    fn test_insert_state_variables_x_front_matter_wins() {
+        let input = DIR.join("test_sans_templater").join("example.md");
+        let output = PathBuf::from("example.html");
+        let mut state: Box<dyn StateTrait> = Box::new(crate::state::state_with_json::StateWithJSON::new());
+        state.insert(String::from("slug"), String::from("alfa"));
+        state.insert(String::from("title"), String::from("Bravo"));
+        let document = MarkdownDocument::from_markdown_str("# Charlie\n", false);
+        let state = super::insert_state_variables(state, &document, &input, &output, &input, "/example.html", DESCRIPTION_LENGTH_DEFAULT);
+        assert_eq!(state.get_value("slug"), Some(StateValue::from("alfa")));
+        assert_eq!(state.get_value("title"), Some(StateValue::from("Bravo")));
+        assert_eq!(state.get_value("word_count"), Some(StateValue::Int(1)));
+    }
+
+    #[test]
+    fn test_insert_state_variables_x_title_plain_text() {
+        let input = DIR.join("test_sans_templater").join("example.md");
+        let output = PathBuf::from("example.html");
+        let state: Box<dyn StateTrait> = Box::new(crate::state::state_with_json::StateWithJSON::new());
+        let document = MarkdownDocument::from_markdown_str("# Hello *world* &amp; `code`\n", false);
+        let state = super::insert_state_variables(state, &document, &input, &output, &input, "/example.html", DESCRIPTION_LENGTH_DEFAULT);
+        assert_eq!(state.get_value("title"), Some(StateValue::from("Hello world & code")));
+    }
+
+    #[test]
+    fn test_insert_state_variables_x_title_from_file_name() {
+        let input = PathBuf::from("posts/hello-world_2026.md");
+        let output = PathBuf::from("posts/hello-world_2026.html");
+        let state: Box<dyn StateTrait> = Box::new(crate::state::state_with_json::StateWithJSON::new());
+        let document = MarkdownDocument::from_markdown_str("## alfa\n", false);
+        let state = super::insert_state_variables(state, &document, &input, &output, &input, "/posts/hello-world_2026.html", DESCRIPTION_LENGTH_DEFAULT);
+        assert_eq!(state.get_value("title"), Some(StateValue::from("Hello world 2026")));
+    }
+
+    #[test]
+    fn test_insert_state_variables_x_summary_marker() {
+        let input = DIR.join("test_sans_templater").join("example.md");
+        let output = PathBuf::from("example.html");
+        let state: Box<dyn StateTrait> = Box::new(crate::state::state_with_json::StateWithJSON::new());
+        let document = MarkdownDocument::from_markdown_str("# Alfa\n\nBravo\n\n- charlie *delta* echo\n\n<!-- more -->\n\nfoxtrot\n", false);
+        let state = super::insert_state_variables(state, &document, &input, &output, &input, "/example.html", 20);
+        assert_eq!(state.get_value("summary"), Some(StateValue::from("<h1 id=\"alfa\">Alfa</h1>\n<p>Bravo</p>\n<ul>\n<li>charlie <em>delta</em> echo</li>\n</ul>")));
+        assert_eq!(state.get_value("description"), Some(StateValue::from("Bravo charlie delta…")));
+    }
+
+    #[test]
+    fn test_insert_state_variables_x_summary_fallback() {
+        let input = DIR.join("test_sans_templater").join("example.md");
+        let output = PathBuf::from("example.html");
+        let state: Box<dyn StateTrait> = Box::new(crate::state::state_with_json::StateWithJSON::new());
+        let document = MarkdownDocument::from_markdown_str("# Alfa\n\nBravo &amp; *charlie*\n", false);
+        let state = super::insert_state_variables(state, &document, &input, &output, &input, "/example.html", DESCRIPTION_LENGTH_DEFAULT);
+        assert_eq!(state.get_value("summary"), Some(StateValue::from("<p>Bravo &amp; <em>charlie</em></p>")));
+        assert_eq!(state.get_value("description"), Some(StateValue::from("Bravo & charlie")));
+    }
+
+    #[test]
+    fn test_insert_state_toc() {
+        let document = super::convert_from_markdown_str_into_markdown_document("# alfa\n## bravo\n### charlie\n", false);
+        let state: Box<dyn StateTrait> = Box::new(crate::state::state_with_json::StateWithJSON::new());
+        let state = super::insert_state_toc(state, &document.headings, 2);
+        assert_eq!(
+            state.get_value("toc_html"),
+            Some(StateValue::from(r##"<ul><li><a href="#alfa">alfa</a><ul><li><a href="#bravo">bravo</a></li></ul></li></ul>"##))
+        );
+        assert_eq!(
+            state.get_value("toc"),
+            Some(StateValue::from(serde_json::json!([
+                {"level": 1, "id": "alfa", "title": "alfa", "children": [
+                    {"level": 2, "id": "bravo", "title": "bravo", "children": []}
+                ]}
+            ])))
+        );
+    }
+
     #[test]
     fn test_read_input_as_mix_text() {
         let input_file_path_buf = crate::testing::TESTS_DIR
@@ -286,7 +771,7 @@ mod tests {
         let html_string: String = super::convert_from_markdown_str_into_html_string(markdown_str);
         assert_eq!(
             html_string,
-            String::from("<h1>alfa</h1>\n<p>bravo</p>\n")
+            String::from("<h1 id=\"alfa\">alfa</h1>\n<p>bravo</p>\n")
         );
     }
 
@@ -0,0 +1,165 @@
+//! Defaults declaration for the front matter of pages in a content directory.
+//!
+//! A content directory can declare its defaults in a data file named
+//! `_defaults.json`, `_defaults.toml`, `_defaults.yaml`, or `_defaults.yml`.
+//!
+//! Example `posts/_defaults.yaml`:
+//!
+//! ```yaml
+//! author: Alice
+//! feed:
+//!   enabled: true
+//! ```
+//!
+//! The declaration applies to the pages in its directory and in its
+//! subdirectories. Defaults cascade downward by deep merge, so nearer
+//! directories take priority, and page front matter wins over all.
+
+use std::path::PathBuf;
+use crate::state::state_trait::StateTrait;
+use crate::state::state_with_json::StateWithJSON;
+use crate::f::from_data_path_buf_into_state::*;
+
+/// The file stem of a defaults declaration file.
+pub const DEFAULTS_FILE_STEM: &str = "_defaults";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DefaultsDeclaration {
+    pub state: StateWithJSON,
+}
+
+impl DefaultsDeclaration {
+
+    /// Load the defaults declaration file in a directory, if any.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let dir = PathBuf::from("posts");
+    /// let defaults: Option<DefaultsDeclaration> = DefaultsDeclaration::load_from_dir(&dir).unwrap();
+    /// ```
+    ///
+    pub fn load_from_dir(dir: &PathBuf) -> Result<Option<Self>, Error> {
+        trace!("DefaultsDeclaration::load_from_dir ➡ dir: {:?}", dir);
+        match find_data_path_buf_in_dir_by_file_stem(dir, DEFAULTS_FILE_STEM) {
+            Some(path_buf) => Ok(Some(Self::load_from_path_buf(&path_buf)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Load a defaults declaration from a data file.
+    pub fn load_from_path_buf(path_buf: &PathBuf) -> Result<Self, Error> {
+        trace!("DefaultsDeclaration::load_from_path_buf ➡ path_buf: {:?}", path_buf);
+        let state = from_data_path_buf_into_state(path_buf)
+        .map_or_else(
+            |err| Err(Error::Load(err)),
+            Ok
+        )?;
+        let mut defaults = DefaultsDeclaration::default();
+        defaults.state.deep_merge(state.as_ref());
+        Ok(defaults)
+    }
+
+    /// Is the given file name reserved for a defaults declaration?
+    pub fn is_defaults_file_name(file_name: &str) -> bool {
+        DATA_FILE_NAME_EXTENSIONS.iter().any(|extension|
+            file_name == format!("{}.{}", DEFAULTS_FILE_STEM, extension)
+        )
+    }
+
+    /// Cascade a nearer declaration over this declaration.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let defaults = parent_defaults.cascade(&child_defaults);
+    /// ```
+    ///
+    pub fn cascade(&self, nearer: &DefaultsDeclaration) -> DefaultsDeclaration {
+        let mut state = self.state.clone();
+        state.deep_merge(&nearer.state);
+        DefaultsDeclaration { state }
+    }
+
+    /// Apply these defaults to a page's state; the page's state wins.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let state: Box<dyn StateTrait> = defaults.apply(state.as_ref());
+    /// ```
+    ///
+    pub fn apply(&self, page_state: &dyn StateTrait) -> Box<dyn StateTrait> {
+        trace!("DefaultsDeclaration::apply");
+        let mut state = self.state.clone();
+        state.deep_merge(page_state);
+        Box::new(state)
+    }
+
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+
+    #[error("Load ➡ {0:?}")]
+    Load(crate::f::from_data_path_buf_into_state::Error),
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use serde_json::json;
+
+    pub static DIR: Lazy<PathBuf> = Lazy::new(||
+        crate::testing::TESTS_DIR
+        .join("src")
+        .join("defaults")
+        .join("defaults_declaration")
+    );
+
+    fn declaration(value: serde_json::Value) -> DefaultsDeclaration {
+        DefaultsDeclaration { state: value.as_object().expect("object").to_owned() }
+    }
+
+    #[test]
+    fn test_load_from_dir_x_present() {
+        let defaults = DefaultsDeclaration::load_from_dir(&DIR.join("present")).expect("load").expect("some");
+        assert_eq!(serde_json::Value::Object(defaults.state), json!({"author": "Alice", "feed": {"enabled": true}}));
+    }
+
+    #[test]
+    fn test_load_from_dir_x_absent() {
+        let defaults = DefaultsDeclaration::load_from_dir(&DIR.join("absent")).expect("load");
+        assert!(defaults.is_none());
+    }
+
+    #[test]
+    fn test_is_defaults_file_name() {
+        assert!(DefaultsDeclaration::is_defaults_file_name("_defaults.yaml"));
+        assert!(DefaultsDeclaration::is_defaults_file_name("_defaults.toml"));
+        assert!(!DefaultsDeclaration::is_defaults_file_name("_defaults.md"));
+        assert!(!DefaultsDeclaration::is_defaults_file_name("defaults.yaml"));
+    }
+
+    #[test]
+    fn test_cascade() {
+        let parent = declaration(json!({"author": "Alice", "feed": {"enabled": true, "limit": 10}}));
+        let child = declaration(json!({"feed": {"limit": 20}}));
+        let actual = parent.cascade(&child);
+        assert_eq!(actual, declaration(json!({"author": "Alice", "feed": {"enabled": true, "limit": 20}})));
+    }
+
+    #[test]
+    fn test_apply() {
+        let defaults = declaration(json!({"author": "Alice", "title": "Untitled"}));
+        let page_state: crate::state::state_with_map::StateWithMap = map!(
+            String::from("title") => String::from("Hello")
+        );
+        let state = defaults.apply(&page_state);
+        let actual = serde_json::to_value(state.to_state_enum()).expect("to_value");
+        assert_eq!(actual, json!({"author": "Alice", "title": "Hello"}));
+    }
+
+}
@@ -0,0 +1,31 @@
+/// Convert from a base URL and a root-relative URL into an absolute URL String.
+///
+/// This joins them with one slash, such as for a feed or sitemap,
+/// where each link must be absolute. No base URL keeps the URL as is.
+///
+/// Example:
+///
+/// ```rust
+/// let url = from_base_url_and_url_into_absolute_url_string(Some("https://example.com/"), "/posts/");
+/// //=> "https://example.com/posts/"
+/// ```
+///
+pub fn from_base_url_and_url_into_absolute_url_string(base_url: Option<&str>, url: &str) -> String {
+    match base_url {
+        Some(base_url) => format!("{}/{}", base_url.trim_end_matches('/'), url.trim_start_matches('/')),
+        None => String::from(url),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_base_url_and_url_into_absolute_url_string() {
+        assert_eq!(from_base_url_and_url_into_absolute_url_string(Some("https://example.com"), "/posts/"), "https://example.com/posts/");
+        assert_eq!(from_base_url_and_url_into_absolute_url_string(Some("https://example.com/blog/"), "/alfa.html"), "https://example.com/blog/alfa.html");
+        assert_eq!(from_base_url_and_url_into_absolute_url_string(None, "/posts/"), "/posts/");
+    }
+
+}
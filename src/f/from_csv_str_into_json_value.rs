@@ -0,0 +1,103 @@
+use crate::types::list::*;
+
+/// Convert from a CSV str into a JSON value.
+///
+/// The first record is the header, and each later record becomes
+/// an object with the header fields as keys, so the result is an array.
+///
+/// Fields can be quoted with double quotes, which can contain commas,
+/// newlines, and doubled double quotes, as described by RFC 4180.
+///
+/// Example:
+///
+/// ```rust
+/// let csv = "name,role\nAda,author\n";
+/// let value = from_csv_str_into_json_value(csv);
+/// //=> [{"name": "Ada", "role": "author"}]
+/// ```
+///
+pub fn from_csv_str_into_json_value(s: &str) -> serde_json::Value {
+    trace!("from_csv_str_into_json_value");
+    let mut records = from_csv_str_into_records(s).into_iter();
+    let header: List<String> = match records.next() {
+        Some(x) => x,
+        None => return serde_json::Value::Array(List::new()),
+    };
+    serde_json::Value::Array(
+        records.map(|record|
+            serde_json::Value::Object(
+                header.iter()
+                .zip(record.into_iter().chain(std::iter::repeat(String::new())))
+                .map(|(key, field)| (key.to_owned(), serde_json::Value::String(field)))
+                .collect()
+            )
+        ).collect()
+    )
+}
+
+/// Parse a CSV str into records of fields, skipping blank lines.
+fn from_csv_str_into_records(s: &str) -> List<List<String>> {
+    let mut records: List<List<String>> = List::new();
+    let mut record: List<String> = List::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => { field.push('"'); chars.next(); },
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                '"' => quoted = true,
+                ',' => record.push(std::mem::take(&mut field)),
+                '\r' => {},
+                '\n' => {
+                    record.push(std::mem::take(&mut field));
+                    if !(record.len() == 1 && record[0].is_empty()) {
+                        records.push(std::mem::take(&mut record));
+                    }
+                    record.clear();
+                },
+                _ => field.push(c),
+            }
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_csv_str_into_json_value() {
+        let s = "name,role\nAda,author\nGrace,editor\n";
+        assert_eq!(
+            from_csv_str_into_json_value(s),
+            json!([{"name": "Ada", "role": "author"}, {"name": "Grace", "role": "editor"}])
+        );
+    }
+
+    #[test]
+    fn test_from_csv_str_into_json_value_x_quotes() {
+        let s = "name,bio\r\n\"Lovelace, Ada\",\"Said \"\"hi\"\"\nthen left\"\r\n\r\nGrace";
+        assert_eq!(
+            from_csv_str_into_json_value(s),
+            json!([{"name": "Lovelace, Ada", "bio": "Said \"hi\"\nthen left"}, {"name": "Grace", "bio": ""}])
+        );
+    }
+
+    #[test]
+    fn test_from_csv_str_into_json_value_x_empty() {
+        assert_eq!(from_csv_str_into_json_value(""), json!([]));
+    }
+
+}
@@ -0,0 +1,49 @@
+use std::path::{Path, PathBuf};
+
+/// Convert from the data directory option and an input path into the data
+/// directory, if any.
+///
+/// The option `--data` wins. Otherwise the default data directory is "data"
+/// in the site root, which is the parent of the input directory, such as
+/// "site/data" for the input "site/posts", if it exists, so the result
+/// doesn't depend on the working directory. An input file's input directory
+/// is its parent.
+///
+/// Example:
+///
+/// ```rust
+/// let data_dir = from_data_dir_and_input_into_data_dir_path_buf(None, Path::new("site/posts"));
+/// //=> Some("site/data")
+/// ```
+///
+pub fn from_data_dir_and_input_into_data_dir_path_buf(data_dir: Option<&Path>, input: &Path) -> Option<PathBuf> {
+    if let Some(x) = data_dir {
+        return Some(x.to_path_buf())
+    }
+    let input_dir = if input.is_file() { input.parent()? } else { input };
+    let root = input_dir.parent().unwrap_or(Path::new(""));
+    Some(root.join(crate::app::args::DATA_DIR_AS_STR)).filter(|x| x.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    pub static DIR: Lazy<PathBuf> = Lazy::new(||
+        crate::testing::TESTS_DIR
+        .join("src")
+        .join("f")
+        .join("from_data_dir_and_input_into_data_dir_path_buf")
+    );
+
+    #[test]
+    fn test_from_data_dir_and_input_into_data_dir_path_buf() {
+        let site = DIR.join("site");
+        assert_eq!(from_data_dir_and_input_into_data_dir_path_buf(None, &site.join("posts")), Some(site.join("data")));
+        assert_eq!(from_data_dir_and_input_into_data_dir_path_buf(None, &site.join("posts").join("alfa.md")), Some(site.join("data")));
+        assert_eq!(from_data_dir_and_input_into_data_dir_path_buf(None, &site), None);
+        assert_eq!(from_data_dir_and_input_into_data_dir_path_buf(Some(Path::new("alfa")), &site.join("posts")), Some(PathBuf::from("alfa")));
+    }
+
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::f::from_data_path_buf_into_state::*;
+use crate::state::state_value::StateValue;
+use crate::f::walkdir_dir_entry_is_visible::*;
+
+/// Data directory file name extensions that we know how to load.
+pub const DATA_DIR_FILE_NAME_EXTENSIONS: [&str; 5] = ["csv", "json", "toml", "yaml", "yml"];
+
+/// Convert from a data directory into a JSON value.
+///
+/// This loads every JSON, TOML, YAML, and CSV file under the directory,
+/// via `from_data_path_into_state_value`,
+/// and nests each file's value by its relative path without extension.
+/// Hidden files and hidden directories are skipped.
+///
+/// Example:
+///
+/// ```rust
+/// let dir = PathBuf::from("data");
+/// let value = from_data_dir_into_json_value(&dir).unwrap();
+/// //=> {"authors": …, "menus": {"main": …}}
+/// ```
+///
+pub fn from_data_dir_into_json_value(dir: &Path) -> Result<serde_json::Value, Error> {
+    trace!("from_data_dir_into_json_value ➡ dir: {:?}", dir);
+    let mut root = StateValue::Map(Default::default());
+    for dir_entry in WalkDir::new(dir).sort_by_file_name().into_iter().filter_entry(|x| x.depth() == 0 || walkdir_dir_entry_is_visible(x)) {
+        let dir_entry = dir_entry
+        .map_or_else(
+            |err| Err(Error::WalkDir(err)),
+            Ok
+        )?;
+        if !dir_entry.file_type().is_file() { continue; }
+        let path = dir_entry.path();
+        let extension = path.extension().map(|x| x.to_string_lossy().to_lowercase()).unwrap_or_default();
+        if !DATA_DIR_FILE_NAME_EXTENSIONS.contains(&extension.as_str()) {
+            trace!("from_data_dir_into_json_value ➡ skip: {:?}", path);
+            continue;
+        }
+        let value = from_data_path_into_state_value(path)
+        .map_or_else(
+            |err| Err(Error::Load(err)),
+            Ok
+        )?;
+        let keys: Vec<String> = path.strip_prefix(dir).unwrap_or(path)
+            .with_extension("")
+            .components()
+            .map(|x| x.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let nested = keys.into_iter().rev().fold(value, |acc, key| {
+            StateValue::Map([(key, acc)].into_iter().collect())
+        });
+        root.deep_merge(nested);
+    }
+    Ok(serde_json::Value::from(root))
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+
+    #[error("WalkDir ➡ {0:?}")]
+    WalkDir(walkdir::Error),
+
+    #[error("Load ➡ {0:?}")]
+    Load(crate::f::from_data_path_buf_into_state::Error),
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use once_cell::sync::Lazy;
+    use serde_json::json;
+
+    pub static DIR: Lazy<PathBuf> = Lazy::new(||
+        crate::testing::TESTS_DIR
+        .join("src")
+        .join("f")
+        .join("from_data_dir_into_json_value")
+    );
+
+    #[test]
+    fn test_from_data_dir_into_json_value() {
+        let actual = from_data_dir_into_json_value(&DIR.join("data")).expect("value");
+        assert_eq!(actual, json!({
+            "authors": [{"name": "Ada", "role": "author"}],
+            "menus": {
+                "main": [{"title": "Home", "url": "/"}],
+                "footer": {"copyright": "Example", "published": "2026-01-02"},
+            },
+            "site": {"title": "Example"},
+        }));
+    }
+
+    #[test]
+    fn test_from_data_dir_into_json_value_x_parse_err() {
+        let result = from_data_dir_into_json_value(&DIR.join("invalid"));
+        assert!(matches!(result, Err(Error::Load(crate::f::from_data_path_buf_into_state::Error::Parse { .. }))));
+    }
+
+}
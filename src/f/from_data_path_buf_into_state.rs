@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+use crate::state::state_trait::StateTrait;
+use crate::state::state_value::StateValue;
+use crate::state::state_with_json::StateWithJSON;
+use crate::f::from_csv_str_into_json_value::*;
+
+/// Data file name extensions that we know how to parse, in lookup order.
+pub const DATA_FILE_NAME_EXTENSIONS: [&str; 4] = ["json", "toml", "yaml", "yml"];
+
+/// Convert from a data file path buffer into state.
+///
+/// This loads a data file via `from_data_path_into_state_value`,
+/// which must be a map, or empty, such as a directory-level settings file,
+/// e.g. `_schema.yaml`.
+///
+/// Example:
+///
+/// ```rust
+/// let path_buf = PathBuf::from("example.yaml");
+/// let state: Box<dyn StateTrait> = from_data_path_buf_into_state(&path_buf).unwrap();
+/// ```
+///
+pub fn from_data_path_buf_into_state(path_buf: &PathBuf) -> Result<Box<dyn StateTrait>, Error> {
+    trace!("from_data_path_buf_into_state ➡ path_buf: {:?}", path_buf);
+    match from_data_path_into_state_value(path_buf)? {
+        StateValue::Null => Ok(Box::new(StateWithJSON::new())),
+        value @ StateValue::Map(_) => match serde_json::Value::from(value) {
+            serde_json::Value::Object(state) => Ok(Box::new(state)),
+            _ => Err(Error::Map { path_buf: path_buf.to_owned() }),
+        },
+        _ => Err(Error::Map { path_buf: path_buf.to_owned() }),
+    }
+}
+
+/// Convert from a data file path into a state value.
+///
+/// This reads a JSON, TOML, YAML, or CSV file, and chooses the parser by
+/// the file name extension. Each parser's value converts via `StateValue`,
+/// so a TOML datetime becomes a string, such as "2026-01-02", like it does
+/// in front matter. A CSV file becomes a list of maps, one per record,
+/// with the header fields as keys.
+///
+/// Example:
+///
+/// ```rust
+/// let value: StateValue = from_data_path_into_state_value(Path::new("authors.csv")).unwrap();
+/// //=> [{"name": "Ada", "role": "author"}]
+/// ```
+///
+pub fn from_data_path_into_state_value(path: &Path) -> Result<StateValue, Error> {
+    trace!("from_data_path_into_state_value ➡ path: {:?}", path);
+    let text = std::fs::read_to_string(path)
+    .map_or_else(
+        |err| Err(Error::Read { path_buf: path.to_path_buf(), err }),
+        Ok
+    )?;
+    let extension = path.extension().map(|x| x.to_string_lossy().to_lowercase());
+    let result: Result<StateValue, String> = match extension.as_deref() {
+        Some("csv") => Ok(StateValue::from(from_csv_str_into_json_value(&text))),
+        Some("json") => serde_json::from_str::<serde_json::Value>(&text).map(StateValue::from).map_err(|err| format!("{:?}", err)),
+        Some("toml") => toml::from_str::<toml::Value>(&text).map(StateValue::from).map_err(|err| format!("{:?}", err)),
+        Some("yaml") | Some("yml") => serde_yaml::from_str::<serde_yaml::Value>(&text).map(StateValue::from).map_err(|err| format!("{:?}", err)),
+        _ => return Err(Error::Extension { path_buf: path.to_path_buf() }),
+    };
+    result
+    .map_or_else(
+        |debug| Err(Error::Parse { path_buf: path.to_path_buf(), debug }),
+        Ok
+    )
+}
+
+/// Find a data file in a directory by its file stem, such as "_schema".
+///
+/// This tries each extension in `DATA_FILE_NAME_EXTENSIONS` in order,
+/// and returns the first path that is a file.
+///
+/// Example:
+///
+/// ```rust
+/// let dir = PathBuf::from("posts");
+/// let path_buf: Option<PathBuf> = find_data_path_buf_in_dir_by_file_stem(&dir, "_schema");
+/// //=> Some("posts/_schema.yaml")
+/// ```
+///
+pub fn find_data_path_buf_in_dir_by_file_stem(dir: &Path, file_stem: &str) -> Option<PathBuf> {
+    DATA_FILE_NAME_EXTENSIONS.iter()
+    .map(|extension| dir.join(format!("{}.{}", file_stem, extension)))
+    .find(|path_buf| path_buf.is_file())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+
+    #[error("Read ➡ path_buf: {path_buf:?}, err: {err:?}")]
+    Read {
+        path_buf: PathBuf,
+        err: std::io::Error,
+    },
+
+    #[error("Parse ➡ path_buf: {path_buf:?}, debug: {debug:?}")]
+    Parse {
+        path_buf: PathBuf,
+        debug: String,
+    },
+
+    #[error("Extension ➡ path_buf: {path_buf:?}")]
+    Extension {
+        path_buf: PathBuf,
+    },
+
+    #[error("Map ➡ path_buf: {path_buf:?}")]
+    Map {
+        path_buf: PathBuf,
+    },
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    pub static DIR: Lazy<PathBuf> = Lazy::new(||
+        crate::testing::TESTS_DIR
+        .join("src")
+        .join("f")
+        .join("from_data_path_buf_into_state")
+    );
+
+    #[test]
+    fn test_from_data_path_buf_into_state_x_json() {
+        let state = from_data_path_buf_into_state(&DIR.join("example.json")).expect("state");
+        assert!(state.contains_key("alfa"));
+    }
+
+    #[test]
+    fn test_from_data_path_buf_into_state_x_toml() {
+        let state = from_data_path_buf_into_state(&DIR.join("example.toml")).expect("state");
+        assert!(state.contains_key("alfa"));
+    }
+
+    #[test]
+    fn test_from_data_path_buf_into_state_x_yaml() {
+        let state = from_data_path_buf_into_state(&DIR.join("example.yaml")).expect("state");
+        assert!(state.contains_key("alfa"));
+    }
+
+    #[test]
+    fn test_from_data_path_buf_into_state_x_extension_err() {
+        let result = from_data_path_buf_into_state(&DIR.join("example.txt"));
+        assert!(matches!(result, Err(Error::Extension { .. })));
+    }
+
+    #[test]
+    fn test_from_data_path_buf_into_state_x_missing_err() {
+        let result = from_data_path_buf_into_state(&DIR.join("missing.json"));
+        assert!(matches!(result, Err(Error::Read { .. })));
+    }
+
+    #[test]
+    fn test_from_data_path_buf_into_state_x_map_err() {
+        let result = from_data_path_buf_into_state(&DIR.join("example.csv"));
+        assert!(matches!(result, Err(Error::Map { .. })));
+    }
+
+    #[test]
+    fn test_from_data_path_into_state_value_x_csv() {
+        let actual = from_data_path_into_state_value(&DIR.join("example.csv")).expect("value");
+        assert_eq!(actual, StateValue::from(serde_json::json!([{"name": "Ada", "role": "author"}])));
+    }
+
+    #[test]
+    fn test_find_data_path_buf_in_dir_by_file_stem_x_present() {
+        let actual = find_data_path_buf_in_dir_by_file_stem(&DIR, "example");
+        assert_eq!(actual, Some(DIR.join("example.json")));
+    }
+
+    #[test]
+    fn test_find_data_path_buf_in_dir_by_file_stem_x_absent() {
+        let actual = find_data_path_buf_in_dir_by_file_stem(&DIR, "missing");
+        assert_eq!(actual, None);
+    }
+
+}
@@ -0,0 +1,73 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\d{4})-(\d{2})-(\d{2})(?:[T ](\d{2}):(\d{2})(?::(\d{2})(?:\.\d+)?)?)?\s*(Z|[+-]\d{2}:?\d{2})?$").expect("regex")
+});
+
+/// Convert from a date str into a SystemTime, such as for a feed date.
+///
+/// The str can be a date such as "2026-01-02", which means midnight UTC,
+/// or a date and time such as "2026-01-02T03:04:05Z" or "2026-01-02 03:04",
+/// with an optional UTC offset such as "+01:00"; no offset means UTC.
+///
+/// Example:
+///
+/// ```rust
+/// let time: Option<SystemTime> = from_date_str_into_system_time("2026-01-02");
+/// ```
+///
+pub fn from_date_str_into_system_time(s: &str) -> Option<SystemTime> {
+    let captures = DATE_REGEX.captures(s.trim())?;
+    let number = |i: usize| -> i64 { captures.get(i).and_then(|x| x.as_str().parse().ok()).unwrap_or(0) };
+    let (year, month, day) = (number(1), number(2), number(3));
+    let (hour, minute, second) = (number(4), number(5), number(6));
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None
+    }
+    let offset = match captures.get(7).map(|x| x.as_str().replace(':', "")) {
+        Some(x) if x != "Z" => {
+            let sign = if x.starts_with('-') { -1 } else { 1 };
+            let hours: i64 = x[1..3].parse().ok()?;
+            let minutes: i64 = x[3..5].parse().ok()?;
+            sign * (hours * 3600 + minutes * 60)
+        },
+        _ => 0,
+    };
+    let seconds = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second - offset;
+    u64::try_from(seconds).ok().map(|x| UNIX_EPOCH + Duration::from_secs(x))
+}
+
+/// Count the days from 1970-01-01 to a proleptic Gregorian date.
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_date_str_into_system_time() {
+        let time = |secs: u64| Some(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(from_date_str_into_system_time("1970-01-02"), time(86400));
+        assert_eq!(from_date_str_into_system_time("2026-01-02"), time(1767312000));
+        assert_eq!(from_date_str_into_system_time("2026-01-02T03:04:05Z"), time(1767312000 + 11045));
+        assert_eq!(from_date_str_into_system_time("2026-01-02 03:04"), time(1767312000 + 11040));
+        assert_eq!(from_date_str_into_system_time("2026-01-02T03:04:05+01:00"), time(1767312000 + 11045 - 3600));
+    }
+
+    #[test]
+    fn test_from_date_str_into_system_time_x_invalid() {
+        assert_eq!(from_date_str_into_system_time("alfa"), None);
+        assert_eq!(from_date_str_into_system_time("2026-13-02"), None);
+        assert_eq!(from_date_str_into_system_time("1969-12-31"), None);
+    }
+
+}
@@ -0,0 +1,67 @@
+use std::ffi::OsString;
+use crate::types::{map::*, set::*};
+
+/// Convert from environment variables into a map of allowed names and values.
+///
+/// This keeps each variable whose name is the prefix then an allowed name,
+/// compared case-insensitively, and returns the allowed name in lowercase.
+/// Any variable that isn't allowed is skipped, so secrets stay private,
+/// and any variable that isn't UTF-8 is skipped, rather than a panic.
+///
+/// Example:
+///
+/// ```rust
+/// let names = set!(String::from("analytics_id"));
+/// let map = from_env_vars_into_map_string_string(std::env::vars_os(), "SITA_", &names);
+/// //=> {"analytics_id": "…"} when the environment has SITA_ANALYTICS_ID
+/// ```
+///
+pub fn from_env_vars_into_map_string_string(
+    vars: impl Iterator<Item = (OsString, OsString)>,
+    prefix: &str,
+    names: &Set<String>,
+) -> Map<String, String> {
+    trace!("from_env_vars_into_map_string_string ➡ prefix: {:?}, names: {:?}", prefix, names);
+    let names: Set<String> = names.iter().map(|x| x.to_lowercase()).collect();
+    vars.filter_map(|(key, value)| {
+        let name = key.to_str()?.strip_prefix(prefix)?.to_lowercase();
+        if names.contains(&name) { Some((name, value.into_string().ok()?)) } else { None }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_env_vars_into_map_string_string() {
+        let vars = vec![
+            (OsString::from("SITA_ANALYTICS_ID"), OsString::from("alfa")),
+            (OsString::from("SITA_SECRET_TOKEN"), OsString::from("bravo")),
+            (OsString::from("ANALYTICS_ID"), OsString::from("charlie")),
+            (OsString::from("SITA_STAGE"), OsString::from("delta")),
+        ];
+        let names = set!(String::from("analytics_id"), String::from("STAGE"));
+        let actual = from_env_vars_into_map_string_string(vars.into_iter(), "SITA_", &names);
+        assert_eq!(actual, map!(
+            String::from("analytics_id") => String::from("alfa"),
+            String::from("stage") => String::from("delta")
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_env_vars_into_map_string_string_x_non_utf8() {
+        use std::os::unix::ffi::OsStringExt;
+        let vars = vec![
+            (OsString::from("SITA_ANALYTICS_ID"), OsString::from_vec(vec![0x61, 0xff])),
+            (OsString::from_vec(vec![0x53, 0xff]), OsString::from("bravo")),
+            (OsString::from("SITA_STAGE"), OsString::from("charlie")),
+        ];
+        let names = set!(String::from("analytics_id"), String::from("stage"));
+        let actual = from_env_vars_into_map_string_string(vars.into_iter(), "SITA_", &names);
+        assert_eq!(actual, map!(String::from("stage") => String::from("charlie")));
+    }
+
+}
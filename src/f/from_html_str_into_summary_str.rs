@@ -0,0 +1,54 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::types::html::*;
+
+pub static REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)<!--\s*more\s*-->").unwrap()
+});
+
+/// Convert from HtmlStr into a summary str.
+///
+/// This finds the first summary marker `<!-- more -->` and returns the HTML before it.
+///
+/// A typical use case is an author choosing how much of a page to show in a listing.
+///
+/// Example:
+//
+/// ```rust
+/// let from: "<p>Alpha</p>\n<!-- more -->\n<p>Bravo</p>";
+/// let to: &str = from_html_str_into_summary_str(&from);
+/// //=> "<p>Alpha</p>"
+/// ```
+///
+pub fn from_html_str_into_summary_str(html_str: &HtmlStr) -> Option<&str> {
+    (*REGEX).find(html_str).map(|x| html_str[..x.start()].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::*;
+    use ::indoc::indoc;
+
+    #[test]
+    fn test_from_html_str_into_summary_str_x_present() {
+        let html_str = indoc!{r#"
+            <h1>alfa</h1>
+            <p>bravo</p>
+            <!--MORE-->
+            <p>charlie</p>
+         "#};
+        assert_some_eq_expr!(from_html_str_into_summary_str(html_str), "<h1>alfa</h1>\n<p>bravo</p>");
+    }
+
+    #[test]
+    fn test_from_html_str_into_summary_str_x_absent() {
+        let html_str = indoc!{r#"
+            <h1>alfa</h1>
+            <p>bravo</p>
+            <!-- charlie -->
+        "#};
+        assert_none!(from_html_str_into_summary_str(html_str));
+    }
+
+}
@@ -0,0 +1,50 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::types::html::*;
+
+pub static TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<!--.*?-->|<[^>]*>").unwrap()
+});
+
+pub static WHITESPACE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\s+").unwrap()
+});
+
+/// Convert from HtmlStr into a plain text String.
+///
+/// This strips the tags and comments, decodes the common entities,
+/// and collapses each run of whitespace into one space.
+///
+/// A typical use case is counting words, or writing a meta description.
+///
+/// Example:
+///
+/// ```rust
+/// let from = "<h1>Alfa</h1>\n<p>Bravo &amp; <em>Charlie</em></p>";
+/// let to: String = from_html_str_into_text_string(&from);
+/// //=> "Alfa Bravo & Charlie"
+/// ```
+///
+pub fn from_html_str_into_text_string(html_str: &HtmlStr) -> String {
+    let s = TAG_REGEX.replace_all(html_str, " ");
+    let s = WHITESPACE_REGEX.replace_all(&s, " ");
+    s.trim()
+    .replace("&lt;", "<")
+    .replace("&gt;", ">")
+    .replace("&quot;", "\"")
+    .replace("&#39;", "'")
+    .replace("&nbsp;", " ")
+    .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_html_str_into_text_string() {
+        let html_str = "<h1>Alfa</h1>\n<p>Bravo &amp; <em>Charlie</em></p>\n<!-- delta -->\n<p>&lt;echo&gt;</p>\n";
+        assert_eq!(from_html_str_into_text_string(html_str), "Alfa Bravo & Charlie <echo>");
+    }
+
+}
@@ -1,4 +1,4 @@
-use pulldown_cmark;
+use crate::markdown::markdown_document::MarkdownDocument;
 
 /// From Markdown str into HTML String.
 /// 
@@ -7,11 +7,13 @@ use pulldown_cmark;
 /// This implementation creates a pulldown parser with our preferred options,
 /// which are defined in `crate::markdown::markdown_parser::parser`.`
 ///
+/// Each heading gets a stable `id` anchor, such as `<h2 id="alfa">Alfa</h2>`.
+///
+/// To get the headings, title, and first paragraph too, see `MarkdownDocument`.
+///
+#[allow(dead_code)]
 pub fn from_markdown_str_into_html_string(markdown_str: &str) -> String {
-    let parser = crate::markdown::markdown_parser::parser(markdown_str);
-    let mut html_string = String::new();
-    pulldown_cmark::html::push_html(&mut html_string, parser);
-    html_string
+    MarkdownDocument::from_markdown_str(markdown_str, false).html
 }
 
 #[cfg(test)]
@@ -22,7 +24,14 @@ mod tests {
     fn test() {
         let markdown_str = "# alfa\nbravo\n";
         let html_string = from_markdown_str_into_html_string(markdown_str);
-        assert_eq!("<h1>alfa</h1>\n<p>bravo</p>\n", html_string);
+        assert_eq!("<h1 id=\"alfa\">alfa</h1>\n<p>bravo</p>\n", html_string);
+    }
+
+    #[test]
+    fn test_x_duplicate_headings() {
+        let markdown_str = "# alfa\n## bravo\n## bravo\n";
+        let html_string = from_markdown_str_into_html_string(markdown_str);
+        assert_eq!("<h1 id=\"alfa\">alfa</h1>\n<h2 id=\"bravo\">bravo</h2>\n<h2 id=\"bravo-1\">bravo</h2>\n", html_string);
     }
 
 }
@@ -0,0 +1,45 @@
+use std::path::Path;
+
+/// The file name that a URL can omit, because servers serve it for its directory.
+pub const INDEX_FILE_NAME_AS_STR: &str = "index.html";
+
+/// Convert from an output relative path into a root-relative URL String.
+///
+/// This joins the path components with slashes, and omits a trailing
+/// `index.html`, so that a directory's index page gets the directory URL.
+///
+/// Example:
+///
+/// ```rust
+/// let url = from_relative_path_into_url_string(&PathBuf::from("posts/alfa.html"));
+/// //=> "/posts/alfa.html"
+///
+/// let url = from_relative_path_into_url_string(&PathBuf::from("posts/index.html"));
+/// //=> "/posts/"
+/// ```
+///
+pub fn from_relative_path_into_url_string(path: &Path) -> String {
+    let mut parts: Vec<String> = path.components()
+        .map(|x| x.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if parts.last().map(|x| x.as_str()) == Some(INDEX_FILE_NAME_AS_STR) {
+        parts.pop();
+        parts.push(String::new());
+    }
+    format!("/{}", parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_from_relative_path_into_url_string() {
+        assert_eq!(from_relative_path_into_url_string(&PathBuf::from("alfa.html")), "/alfa.html");
+        assert_eq!(from_relative_path_into_url_string(&PathBuf::from("posts/alfa.html")), "/posts/alfa.html");
+        assert_eq!(from_relative_path_into_url_string(&PathBuf::from("posts/index.html")), "/posts/");
+        assert_eq!(from_relative_path_into_url_string(&PathBuf::from("index.html")), "/");
+    }
+
+}
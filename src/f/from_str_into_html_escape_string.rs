@@ -0,0 +1,35 @@
+/// Convert from a str into an HTML escaped String, for text or an attribute value.
+///
+/// Example:
+///
+/// ```rust
+/// let s: String = from_str_into_html_escape_string("<a href=\"#\">Q&A</a>");
+/// //=> "&lt;a href=&quot;#&quot;&gt;Q&amp;A&lt;/a&gt;"
+/// ```
+///
+pub fn from_str_into_html_escape_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_into_html_escape_string() {
+        assert_eq!(from_str_into_html_escape_string("<a href=\"#\">Q&A's</a>"), "&lt;a href=&quot;#&quot;&gt;Q&amp;A&#39;s&lt;/a&gt;");
+        assert_eq!(from_str_into_html_escape_string("alfa"), "alfa");
+    }
+
+}
@@ -0,0 +1,38 @@
+/// Convert from a str into a slug String, for a URL path or an anchor id.
+///
+/// This lowercases the text, keeps letters and digits, and joins
+/// each run of anything else into one hyphen, without any at the ends.
+///
+/// Example:
+///
+/// ```rust
+/// let slug: String = from_str_into_slug_string("Hello, World!");
+/// //=> "hello-world"
+/// ```
+///
+pub fn from_str_into_slug_string(s: &str) -> String {
+    let mut slug = String::with_capacity(s.len());
+    for c in s.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    while slug.ends_with('-') { slug.pop(); }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_into_slug_string() {
+        assert_eq!(from_str_into_slug_string("Hello, World!"), "hello-world");
+        assert_eq!(from_str_into_slug_string("  --Alfa_Bravo  2026--  "), "alfa-bravo-2026");
+        assert_eq!(from_str_into_slug_string("Crème Brûlée"), "crème-brûlée");
+        assert_eq!(from_str_into_slug_string("!!!"), "");
+    }
+
+}
@@ -0,0 +1,35 @@
+/// Convert from a str, such as a file name stem, into a title String.
+///
+/// This splits the text into words on hyphens, underscores, and whitespace,
+/// joins the words with one space, and capitalizes the first letter.
+///
+/// A typical use case is a fallback title for a page that has no headline.
+///
+/// Example:
+///
+/// ```rust
+/// let title: String = from_str_into_title_string("hello-world_2026");
+/// //=> "Hello world 2026"
+/// ```
+///
+pub fn from_str_into_title_string(s: &str) -> String {
+    let words = s.split(|c: char| c == '-' || c == '_' || c.is_whitespace()).filter(|x| !x.is_empty()).collect::<Vec<&str>>().join(" ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_into_title_string() {
+        assert_eq!(from_str_into_title_string("hello-world_2026"), "Hello world 2026");
+        assert_eq!(from_str_into_title_string("--élan--vital--"), "Élan vital");
+        assert_eq!(from_str_into_title_string(""), "");
+    }
+
+}
@@ -0,0 +1,49 @@
+/// Convert from a str into a truncated String, cut on a word boundary.
+///
+/// When the text has more characters than the length, this cuts the text
+/// at the last whitespace that fits, then appends an ellipsis "…".
+/// When one word is longer than the length, this cuts inside the word.
+///
+/// Example:
+///
+/// ```rust
+/// let s: String = from_str_into_truncated_string("alfa bravo charlie", 12);
+/// //=> "alfa bravo…"
+/// ```
+///
+pub fn from_str_into_truncated_string(s: &str, length: usize) -> String {
+    if s.chars().count() <= length {
+        return String::from(s)
+    }
+    let end = s.char_indices().nth(length).map_or(s.len(), |(i, _)| i);
+    let head = &s[..end];
+    let head = match head.rfind(char::is_whitespace) {
+        Some(i) if !s[end..].starts_with(char::is_whitespace) => &head[..i],
+        _ => head,
+    };
+    let head = head.trim_end().trim_end_matches(|c: char| c.is_ascii_punctuation());
+    format!("{}…", head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_into_truncated_string_x_short() {
+        assert_eq!(from_str_into_truncated_string("alfa bravo", 10), "alfa bravo");
+    }
+
+    #[test]
+    fn test_from_str_into_truncated_string_x_long() {
+        assert_eq!(from_str_into_truncated_string("alfa bravo charlie", 12), "alfa bravo…");
+        assert_eq!(from_str_into_truncated_string("alfa bravo charlie", 10), "alfa bravo…");
+        assert_eq!(from_str_into_truncated_string("alfa, bravo charlie", 8), "alfa…");
+    }
+
+    #[test]
+    fn test_from_str_into_truncated_string_x_one_long_word() {
+        assert_eq!(from_str_into_truncated_string("alfabravocharlie", 4), "alfa…");
+    }
+
+}
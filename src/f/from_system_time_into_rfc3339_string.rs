@@ -0,0 +1,48 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Convert from a SystemTime into an RFC 3339 String in UTC, such as for an Atom feed.
+///
+/// Example:
+///
+/// ```rust
+/// let s: String = from_system_time_into_rfc3339_string(UNIX_EPOCH);
+/// //=> "1970-01-01T00:00:00Z"
+/// ```
+///
+pub fn from_system_time_into_rfc3339_string(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map(|x| x.as_secs()).unwrap_or(0) as i64;
+    let (days, seconds) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60
+    )
+}
+
+/// Convert from days since 1970-01-01 into a proleptic Gregorian (year, month, day).
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_from_system_time_into_rfc3339_string() {
+        assert_eq!(from_system_time_into_rfc3339_string(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(from_system_time_into_rfc3339_string(UNIX_EPOCH + Duration::from_secs(1767312000 + 11045)), "2026-01-02T03:04:05Z");
+        assert_eq!(from_system_time_into_rfc3339_string(UNIX_EPOCH + Duration::from_secs(951782400)), "2000-02-29T00:00:00Z");
+    }
+
+}
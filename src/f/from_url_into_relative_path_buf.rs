@@ -0,0 +1,55 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Convert from a root-relative URL into an output relative PathBuf,
+/// such as for a permalink or a redirect page, which is the reverse of
+/// `from_relative_path_into_url_string`.
+///
+/// A URL without a file extension is a directory, so gets its index file,
+/// with the file name extension. A URL that escapes the output directory,
+/// such as "/../x", or an absolute URL, such as "https://example.com/",
+/// has none.
+///
+/// Example:
+///
+/// ```rust
+/// let path = from_url_into_relative_path_buf("/blog/2026/alfa/", Path::new("html"));
+/// //=> Some("blog/2026/alfa/index.html")
+///
+/// let path = from_url_into_relative_path_buf("/alfa.html", Path::new("html"));
+/// //=> Some("alfa.html")
+/// ```
+///
+pub fn from_url_into_relative_path_buf(url: &str, extension: &Path) -> Option<PathBuf> {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    if path.starts_with("//") || path.contains(':') {
+        return None
+    }
+    let relative = Path::new(path.trim_start_matches('/'));
+    if relative.components().any(|x| !matches!(x, Component::Normal(_))) {
+        return None
+    }
+    if path.ends_with('/') || path.is_empty() || relative.extension().is_none() {
+        Some(relative.join("index").with_extension(extension))
+    } else {
+        Some(relative.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_url_into_relative_path_buf() {
+        let html = Path::new("html");
+        assert_eq!(from_url_into_relative_path_buf("/blog/2026/alfa/", html), Some(PathBuf::from("blog/2026/alfa/index.html")));
+        assert_eq!(from_url_into_relative_path_buf("blog/alfa", html), Some(PathBuf::from("blog/alfa/index.html")));
+        assert_eq!(from_url_into_relative_path_buf("/alfa.html?x=1#top", html), Some(PathBuf::from("alfa.html")));
+        assert_eq!(from_url_into_relative_path_buf("/", html), Some(PathBuf::from("index.html")));
+        assert_eq!(from_url_into_relative_path_buf("/alfa/", Path::new("htm")), Some(PathBuf::from("alfa/index.htm")));
+        assert_eq!(from_url_into_relative_path_buf("/../alfa/", html), None);
+        assert_eq!(from_url_into_relative_path_buf("https://example.com/", html), None);
+        assert_eq!(from_url_into_relative_path_buf("//example.com/", html), None);
+    }
+
+}
@@ -27,9 +27,22 @@ pub(crate) mod app { // Application
     pub(crate) mod run; // Run function that handles everything
 }
 
+pub(crate) mod defaults {
+    pub(crate) mod defaults_declaration;
+}
+
 pub(crate) mod f { // Functions
+    pub(crate) mod from_base_url_and_url_into_absolute_url_string; // from base URL and root-relative URL into absolute URL String, such as for a feed
+    pub(crate) mod from_csv_str_into_json_value; // from CSV str into JSON value, such as an array of objects
+    pub(crate) mod from_data_dir_and_input_into_data_dir_path_buf; // from data dir option and input path into data dir PathBuf, such as "site/data"
+    pub(crate) mod from_data_dir_into_json_value; // from data directory into JSON value, such as for the `data` variable
+    pub(crate) mod from_data_path_buf_into_state; // from data file PathBuf into state, such as JSON, TOML, YAML, CSV
+    pub(crate) mod from_date_str_into_system_time; // from date str into SystemTime, such as "2026-01-02" for a feed
+    pub(crate) mod from_env_vars_into_map_string_string; // from environment variables into Map<String, String>, such as for the `env` variable
     pub(crate) mod from_html_str_into_headline_str; // from HtmlStr into headline str
     pub(crate) mod from_html_str_into_paragraph_str; // from HtmlStr into paragraph str
+    pub(crate) mod from_html_str_into_summary_str; // from HtmlStr into summary str, before the marker `<!-- more -->`
+    pub(crate) mod from_html_str_into_text_string; // from HtmlStr into plain text String
     pub(crate) mod from_markdown_str_into_html_string; // from Markdown str into HTML String    
     pub(crate) mod from_list_pathable_string_into_list_path_buf; // from List<PathableString> into List<PathBuf>
     pub(crate) mod from_list_str_into_map_string_string; // from List<&str> into Map<String, String>
@@ -37,7 +50,14 @@ pub(crate) mod f { // Functions
     pub(crate) mod from_input_path_buf_and_output_path_buf_into_map; // from input path buffer and output path buffer into Map<PathBuf, PathBuf>
     pub(crate) mod from_path_buf_into_sibling_extension; // from PathBuf into sibling PathBuf
     pub(crate) mod from_pathable_string_into_list_path_buf; // from PathableString into List<PathBuf>
+    pub(crate) mod from_relative_path_into_url_string; // from output relative Path into URL String
     pub(crate) mod from_set_pathable_string_into_set_path_buf; // from Set<PathableString> into Set<PathBuf>
+    pub(crate) mod from_str_into_html_escape_string; // from str into HTML escaped String
+    pub(crate) mod from_str_into_slug_string; // from str into slug String, such as for a URL or anchor
+    pub(crate) mod from_str_into_title_string; // from str into title String, such as from a file name stem
+    pub(crate) mod from_str_into_truncated_string; // from str into truncated String, cut on a word boundary
+    pub(crate) mod from_system_time_into_rfc3339_string; // from SystemTime into RFC 3339 String, such as for an Atom feed
+    pub(crate) mod from_url_into_relative_path_buf; // from root-relative URL into output relative PathBuf, such as for a permalink
     pub(crate) mod remove_dir_if_exists;
     pub(crate) mod remove_file_if_exists;
     pub(crate) mod vet_input_file_path_buf_exists; // Vet an input file PathBuf exists.
@@ -49,7 +69,10 @@ pub(crate) mod f { // Functions
 }
 
 pub(crate) mod markdown {
+    pub(crate) mod markdown_document;
+    pub(crate) mod markdown_heading;
     pub(crate) mod markdown_parser;
+    pub(crate) mod markdown_toc;
 }
 
 pub(crate) mod matter {
@@ -66,9 +89,31 @@ pub(crate) mod rewriting {
     pub(crate) mod lol;
 }
 
+pub(crate) mod schema {
+    pub(crate) mod schema_declaration;
+    pub(crate) mod schema_field;
+    pub(crate) mod schema_type;
+    pub(crate) mod schema_violation;
+}
+
+pub(crate) mod site {
+    pub(crate) mod site_archive;
+    pub(crate) mod site_feed;
+    pub(crate) mod site_model;
+    pub(crate) mod site_page;
+    pub(crate) mod site_language;
+    pub(crate) mod site_paginator;
+    pub(crate) mod site_permalink;
+    pub(crate) mod site_redirect;
+    pub(crate) mod site_search;
+    pub(crate) mod site_sitemap;
+    pub(crate) mod site_taxonomy;
+}
+
 pub(crate) mod state {
     pub(crate) mod state_enum;
     pub(crate) mod state_trait;
+    pub(crate) mod state_value;
     pub(crate) mod state_with_map;
     pub(crate) mod state_with_json;
     pub(crate) mod state_with_toml;
@@ -0,0 +1,175 @@
+//! Markdown document, i.e. the HTML and the facts found in the pulldown-cmark event stream.
+//!
+//! The title and description come from the events rather than from the HTML,
+//! so inline markup such as `# Hello *world*` becomes plain text "Hello world",
+//! and entities such as `&amp;` are already decoded by the parser.
+
+use pulldown_cmark::{Event, HeadingLevel, Tag, TagEnd};
+use crate::types::list::*;
+use crate::markdown::markdown_heading::*;
+use crate::f::from_html_str_into_summary_str::REGEX as SUMMARY_MARKER_REGEX;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkdownDocument {
+
+    /// The HTML, with each heading having an `id` anchor.
+    pub html: String,
+
+    /// The headings in document order, such as for a table of contents.
+    pub headings: List<MarkdownHeading>,
+
+    /// The plain text of the first H1, if any.
+    pub title: Option<String>,
+
+    /// The plain text of the first paragraph, if any.
+    pub paragraph: Option<String>,
+
+    /// The HTML of the first paragraph, if any, such as for a summary.
+    pub paragraph_html: Option<String>,
+
+    /// The plain text before the summary marker `<!-- more -->`, without
+    /// any headings, if there's a marker, such as for a description.
+    pub summary_text: Option<String>,
+
+    /// The plain text of the whole document, with whitespace collapsed,
+    /// such as for a search index.
+    pub text: String,
+
+}
+
+impl MarkdownDocument {
+
+    /// Parse a Markdown str into a document.
+    ///
+    /// When `remove_title` is true, then the first H1 is removed from the HTML
+    /// and from the headings, so a template can show the title once.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let document = MarkdownDocument::from_markdown_str("# Hello *world*\nalfa &amp; bravo", false);
+    /// //=> MarkdownDocument { title: Some("Hello world"), paragraph: Some("alfa & bravo"), … }
+    /// ```
+    ///
+    pub fn from_markdown_str(markdown_str: &str, remove_title: bool) -> Self {
+        trace!("MarkdownDocument::from_markdown_str ➡ remove_title: {:?}", remove_title);
+        let mut events: List<Event> = crate::markdown::markdown_parser::parser(markdown_str).collect();
+        let mut headings = insert_heading_ids(&mut events);
+        let title = headings.iter().find(|x| x.level == 1).map(|x| x.text.clone());
+        let paragraph = from_events_into_first_paragraph_text_string(&events);
+        let paragraph_html = from_events_into_first_paragraph_html_string(&events);
+        let summary_text = from_events_into_summary_text_string(&events);
+        if remove_title {
+            if let Some(range) = find_first_h1_range(&events) {
+                events.drain(range);
+                if let Some(i) = headings.iter().position(|x| x.level == 1) {
+                    headings.remove(i);
+                }
+            }
+        }
+        let text = from_events_into_text_string(&events);
+        let mut html = String::new();
+        pulldown_cmark::html::push_html(&mut html, events.into_iter());
+        MarkdownDocument { html, headings, title, paragraph, paragraph_html, summary_text, text }
+    }
+
+}
+
+/// Find the plain text of the first paragraph, with whitespace collapsed.
+fn from_events_into_first_paragraph_text_string(events: &[Event]) -> Option<String> {
+    let start = events.iter().position(|x| matches!(x, Event::Start(Tag::Paragraph)))?;
+    let mut text = String::new();
+    for event in &events[start + 1..] {
+        match event {
+            Event::End(TagEnd::Paragraph) => break,
+            Event::Text(x) | Event::Code(x) => text.push_str(x),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {},
+        }
+    }
+    let text = text.split_whitespace().collect::<List<&str>>().join(" ");
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// Find the HTML of the first paragraph, from its start through its end.
+fn from_events_into_first_paragraph_html_string(events: &[Event]) -> Option<String> {
+    let start = events.iter().position(|x| matches!(x, Event::Start(Tag::Paragraph)))?;
+    let end = events[start..].iter().position(|x| matches!(x, Event::End(TagEnd::Paragraph)))?;
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events[start..=start + end].iter().cloned());
+    Some(String::from(html.trim_end()))
+}
+
+/// Find the plain text before the summary marker `<!-- more -->`, if any,
+/// without any headings, so a description doesn't repeat the title.
+fn from_events_into_summary_text_string(events: &[Event]) -> Option<String> {
+    let end = events.iter().position(|x| matches!(x, Event::Html(s) | Event::InlineHtml(s) if SUMMARY_MARKER_REGEX.is_match(s)))?;
+    let mut in_heading = false;
+    let events: List<Event> = events[..end].iter().filter(|x| match x {
+        Event::Start(Tag::Heading { .. }) => { in_heading = true; false },
+        Event::End(TagEnd::Heading(_)) => { in_heading = false; false },
+        _ => !in_heading,
+    }).cloned().collect();
+    Some(from_events_into_text_string(&events))
+}
+
+/// Find the plain text of all the events, with whitespace collapsed,
+/// and with a space between blocks, such as between paragraphs.
+fn from_events_into_text_string(events: &[Event]) -> String {
+    let mut text = String::new();
+    for event in events {
+        match event {
+            Event::Text(x) | Event::Code(x) => text.push_str(x),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {},
+        }
+    }
+    text.split_whitespace().collect::<List<&str>>().join(" ")
+}
+
+/// Find the event index range of the first H1, from its start through its end.
+fn find_first_h1_range(events: &[Event]) -> Option<std::ops::Range<usize>> {
+    let start = events.iter().position(|x| matches!(x, Event::Start(Tag::Heading { level: HeadingLevel::H1, .. })))?;
+    let end = events[start..].iter().position(|x| matches!(x, Event::End(TagEnd::Heading(HeadingLevel::H1))))?;
+    Some(start..start + end + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_markdown_str() {
+        let document = MarkdownDocument::from_markdown_str("# Hello *world* &amp; `code`\n\nalfa\n**bravo** &lt;charlie&gt;\n", false);
+        assert_eq!(document.html, "<h1 id=\"hello-world-code\">Hello <em>world</em> &amp; <code>code</code></h1>\n<p>alfa\n<strong>bravo</strong> &lt;charlie&gt;</p>\n");
+        assert_eq!(document.title, Some(String::from("Hello world & code")));
+        assert_eq!(document.paragraph, Some(String::from("alfa bravo <charlie>")));
+        assert_eq!(document.paragraph_html, Some(String::from("<p>alfa\n<strong>bravo</strong> &lt;charlie&gt;</p>")));
+        assert_eq!(document.summary_text, None);
+        assert_eq!(document.headings.len(), 1);
+        assert_eq!(document.text, "Hello world & code alfa bravo <charlie>");
+    }
+
+    #[test]
+    fn test_from_markdown_str_x_absent() {
+        let document = MarkdownDocument::from_markdown_str("## alfa\n\n- bravo\n", false);
+        assert_eq!(document.title, None);
+        assert_eq!(document.paragraph, None);
+    }
+
+    #[test]
+    fn test_from_markdown_str_x_summary_marker() {
+        let document = MarkdownDocument::from_markdown_str("# Alfa\n\nBravo &amp; *charlie*\n\n## Delta\n\n<!-- more -->\n\necho\n", false);
+        assert_eq!(document.summary_text, Some(String::from("Bravo & charlie")));
+    }
+
+    #[test]
+    fn test_from_markdown_str_x_remove_title() {
+        let document = MarkdownDocument::from_markdown_str("# alfa\n\n## bravo\n\ncharlie\n", true);
+        assert_eq!(document.html, "<h2 id=\"bravo\">bravo</h2>\n<p>charlie</p>\n");
+        assert_eq!(document.title, Some(String::from("alfa")));
+        assert_eq!(document.headings.iter().map(|x| x.id.as_str()).collect::<List<&str>>(), vec!["bravo"]);
+        assert_eq!(document.text, "bravo charlie");
+    }
+
+}
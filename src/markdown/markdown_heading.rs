@@ -0,0 +1,113 @@
+//! Markdown heading, i.e. one heading found in the pulldown-cmark event stream.
+
+use pulldown_cmark::{CowStr, Event, Tag, TagEnd};
+use crate::types::{list::*, set::*};
+use crate::f::from_str_into_slug_string::*;
+
+/// The anchor id prefix for a heading that has no letters or digits.
+pub const HEADING_ID_FALLBACK: &str = "section";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownHeading {
+    pub level: u8,
+    pub id: String,
+    pub text: String,
+}
+
+/// Give each heading a stable `id` anchor, and return the headings in order.
+///
+/// A heading keeps any id that it declares via heading attributes,
+/// such as `# Alfa {#bravo}`; otherwise its id is the slug of its text.
+/// A repeated id gets a numeric suffix, such as "alfa", "alfa-1", "alfa-2",
+/// and avoids every declared id, even one that comes later in the text.
+///
+/// Example:
+///
+/// ```rust
+/// let mut events: Vec<Event> = parser("# Alfa\n## Bravo").collect();
+/// let headings = insert_heading_ids(&mut events);
+/// //=> [MarkdownHeading { level: 1, id: "alfa", … }, MarkdownHeading { level: 2, id: "bravo", … }]
+/// ```
+///
+pub fn insert_heading_ids(events: &mut [Event]) -> List<MarkdownHeading> {
+    let mut headings: List<MarkdownHeading> = List::new();
+    let mut ids: Set<String> = events.iter().filter_map(|x| match x {
+        Event::Start(Tag::Heading { id: Some(id), .. }) => Some(id.to_string()),
+        _ => None,
+    }).collect();
+    let mut i = 0;
+    while i < events.len() {
+        if let Event::Start(Tag::Heading { level, id, .. }) = &events[i] {
+            let level = *level as u8;
+            let declared = id.as_ref().map(|x| x.to_string());
+            let mut text = String::new();
+            let mut j = i + 1;
+            while j < events.len() {
+                match &events[j] {
+                    Event::End(TagEnd::Heading(_)) => break,
+                    Event::Text(x) | Event::Code(x) => text.push_str(x),
+                    Event::SoftBreak | Event::HardBreak => text.push(' '),
+                    _ => {},
+                }
+                j += 1;
+            }
+            let id = match declared {
+                Some(x) => x,
+                None => unique_id(&text, &ids),
+            };
+            ids.insert(id.clone());
+            if let Event::Start(Tag::Heading { id: x, .. }) = &mut events[i] {
+                *x = Some(CowStr::from(id.clone()));
+            }
+            headings.push(MarkdownHeading { level, id, text: text.trim().to_owned() });
+            i = j;
+        }
+        i += 1;
+    }
+    headings
+}
+
+fn unique_id(text: &str, ids: &Set<String>) -> String {
+    let slug = from_str_into_slug_string(text);
+    let base = if slug.is_empty() { String::from(HEADING_ID_FALLBACK) } else { slug };
+    if !ids.contains(&base) { return base }
+    (1..).map(|n| format!("{}-{}", base, n)).find(|x| !ids.contains(x)).unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headings(markdown: &str) -> List<MarkdownHeading> {
+        let mut events: Vec<Event> = crate::markdown::markdown_parser::parser(markdown).collect();
+        insert_heading_ids(&mut events)
+    }
+
+    #[test]
+    fn test_insert_heading_ids() {
+        let actual = headings("# Alfa *Bravo*\n\n## `charlie`\n\n## Alfa Bravo\n\n### !!!\n\n## Delta {#echo}\n");
+        assert_eq!(actual, vec![
+            MarkdownHeading { level: 1, id: String::from("alfa-bravo"), text: String::from("Alfa Bravo") },
+            MarkdownHeading { level: 2, id: String::from("charlie"), text: String::from("charlie") },
+            MarkdownHeading { level: 2, id: String::from("alfa-bravo-1"), text: String::from("Alfa Bravo") },
+            MarkdownHeading { level: 3, id: String::from("section"), text: String::from("!!!") },
+            MarkdownHeading { level: 2, id: String::from("echo"), text: String::from("Delta") },
+        ]);
+    }
+
+    #[test]
+    fn test_insert_heading_ids_x_later_declared_id() {
+        let actual = headings("# Alfa
+
+# Bravo {#alfa}
+
+# Alfa
+");
+        assert_eq!(actual, vec![
+            MarkdownHeading { level: 1, id: String::from("alfa-1"), text: String::from("Alfa") },
+            MarkdownHeading { level: 1, id: String::from("alfa"), text: String::from("Bravo") },
+            MarkdownHeading { level: 1, id: String::from("alfa-2"), text: String::from("Alfa") },
+        ]);
+    }
+
+}
@@ -0,0 +1,125 @@
+//! Markdown table of contents, i.e. a nested tree of headings.
+//!
+//! A page's table of contents is available to templates two ways:
+//!
+//! * `toc`: a list of entries, each with `level`, `id`, `title`, `children`.
+//!
+//! * `toc_html`: a nested HTML list of links, such as for a sidebar.
+
+use serde::Serialize;
+use crate::types::list::*;
+use crate::markdown::markdown_heading::MarkdownHeading;
+use crate::f::from_str_into_html_escape_string::*;
+
+/// The default deepest heading level to include, i.e. h1, h2, h3.
+pub const TOC_DEPTH_DEFAULT: u8 = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TocEntry {
+    pub level: u8,
+    pub id: String,
+    pub title: String,
+    pub children: List<TocEntry>,
+}
+
+/// Build a nested table of contents from headings, down to the depth level.
+///
+/// A heading nests under the nearest previous heading with a lower level.
+///
+/// Example:
+///
+/// ```rust
+/// let toc = from_headings_into_toc(&headings, 3);
+/// ```
+///
+pub fn from_headings_into_toc(headings: &[MarkdownHeading], depth: u8) -> List<TocEntry> {
+    let mut toc: List<TocEntry> = List::new();
+    for heading in headings.iter().filter(|x| x.level <= depth) {
+        let entry = TocEntry {
+            level: heading.level,
+            id: heading.id.clone(),
+            title: heading.text.clone(),
+            children: List::new(),
+        };
+        let mut siblings = &mut toc;
+        while siblings.last().is_some_and(|x| x.level < entry.level) {
+            siblings = &mut siblings.last_mut().unwrap().children;
+        }
+        siblings.push(entry);
+    }
+    toc
+}
+
+/// Render a table of contents as nested HTML lists of links.
+///
+/// Example:
+///
+/// ```rust
+/// let html = from_toc_into_html_string(&toc);
+/// //=> "<ul><li><a href=\"#alfa\">Alfa</a></li></ul>"
+/// ```
+///
+pub fn from_toc_into_html_string(toc: &[TocEntry]) -> String {
+    if toc.is_empty() { return String::new() }
+    let mut html = String::from("<ul>");
+    for entry in toc {
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a>{}</li>",
+            from_str_into_html_escape_string(&entry.id),
+            from_str_into_html_escape_string(&entry.title),
+            from_toc_into_html_string(&entry.children),
+        ));
+    }
+    html.push_str("</ul>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading(level: u8, id: &str) -> MarkdownHeading {
+        MarkdownHeading { level, id: String::from(id), text: id.to_uppercase() }
+    }
+
+    fn entry(level: u8, id: &str, children: List<TocEntry>) -> TocEntry {
+        TocEntry { level, id: String::from(id), title: id.to_uppercase(), children }
+    }
+
+    #[test]
+    fn test_from_headings_into_toc() {
+        let headings = vec![
+            heading(1, "alfa"),
+            heading(2, "bravo"),
+            heading(3, "charlie"),
+            heading(4, "delta"),
+            heading(2, "echo"),
+        ];
+        let actual = from_headings_into_toc(&headings, 3);
+        assert_eq!(actual, vec![
+            entry(1, "alfa", vec![
+                entry(2, "bravo", vec![
+                    entry(3, "charlie", vec![]),
+                ]),
+                entry(2, "echo", vec![]),
+            ]),
+        ]);
+    }
+
+    #[test]
+    fn test_from_headings_into_toc_x_starts_deeper() {
+        let headings = vec![heading(2, "alfa"), heading(1, "bravo")];
+        let actual = from_headings_into_toc(&headings, 2);
+        assert_eq!(actual, vec![entry(2, "alfa", vec![]), entry(1, "bravo", vec![])]);
+    }
+
+    #[test]
+    fn test_from_toc_into_html_string() {
+        let toc = vec![entry(1, "alfa", vec![entry(2, "b&c", vec![])])];
+        assert_eq!(
+            from_toc_into_html_string(&toc),
+            r##"<ul><li><a href="#alfa">ALFA</a><ul><li><a href="#b&amp;c">B&amp;C</a></li></ul></li></ul>"##
+        );
+    }
+
+}
@@ -1,5 +1,7 @@
+use std::path::Path;
 use ::lol_html::{element, rewrite_str, RewriteStrSettings}; // , HtmlRewriter, Settings
 use lol_html::html_content::Element;
+use crate::types::map::*;
 
 pub fn rewrite_href_from_http_to_https(e: &mut Element) {
     if let Some(mut x) = e.get_attribute("href") {
@@ -10,20 +12,110 @@ pub fn rewrite_href_from_http_to_https(e: &mut Element) {
     }
 }
 
-pub fn rewrite_href_from_md_to_html(e: &mut Element) {
+/// Rewrite a Markdown link, such as "alfa.md", into its output link,
+/// with the output file name extension, such as "alfa.html" or "alfa.htm".
+pub fn rewrite_href_from_md_to_html(e: &mut Element, extension: &Path) {
     if let Some(mut x) = e.get_attribute("href") {
         if x.ends_with(".md") {
-            x.replace_range((x.len()-3).., ".html");
+            x.replace_range((x.len()-3).., &format!(".{}", extension.to_string_lossy()));
             e.set_attribute("href", &x).expect("set_attribute");
         }
     }
 }
 
-pub fn rewrite(s: &str) -> String {
+/// Rewrite a Markdown link, such as "alfa.md#top", into its page URL,
+/// such as "/blog/2026/alfa/#top", via the permalinks, i.e. a map of each
+/// page's root-relative input path, such as "/posts/alfa.md", to its URL.
+///
+/// A relative link resolves from the page's input directory URL, such as "/posts/".
+/// A link to an unknown page is kept, for `rewrite_href_from_md_to_html`.
+pub fn rewrite_href_from_md_to_permalink(e: &mut Element, dir_url: &str, permalinks: &Map<String, String>) {
+    if let Some(x) = e.get_attribute("href") {
+        if let Some(x) = from_md_url_into_permalink(&x, dir_url, permalinks) {
+            e.set_attribute("href", &x).expect("set_attribute");
+        }
+    }
+}
+
+/// Convert from a Markdown link URL into its page URL via the permalinks, if any,
+/// keeping any query and fragment.
+///
+/// Example:
+///
+/// ```rust
+/// let permalinks = map!(String::from("/posts/alfa.md") => String::from("/blog/alfa/"));
+/// let url = from_md_url_into_permalink("alfa.md#top", "/posts/", &permalinks);
+/// //=> Some("/blog/alfa/#top")
+/// ```
+///
+pub fn from_md_url_into_permalink(url: &str, dir_url: &str, permalinks: &Map<String, String>) -> Option<String> {
+    let (path, rest) = url.split_at(url.find(['?', '#']).unwrap_or(url.len()));
+    if !path.ends_with(".md") {
+        return None
+    }
+    let path = match path.starts_with('/') {
+        true => String::from(path),
+        false => from_relative_url_into_root_url(path, dir_url)?,
+    };
+    permalinks.get(&path).map(|x| format!("{}{}", x, rest))
+}
+
+/// Rewrite a relative link attribute, such as "img/alfa.png",
+/// into a root-relative link, such as "/img/alfa.png", from the page's
+/// directory URL, such as "/".
+pub fn rewrite_link_from_relative_to_root(e: &mut Element, attribute: &str, dir_url: &str) {
+    if let Some(x) = e.get_attribute(attribute) {
+        if let Some(x) = from_relative_url_into_root_url(&x, dir_url) {
+            e.set_attribute(attribute, &x).expect("set_attribute");
+        }
+    }
+}
+
+/// Convert from a relative URL into a root-relative URL, from a directory URL,
+/// or None when the URL is already root-relative or absolute, or is a fragment,
+/// such as "#top", or has a scheme, such as "https:" or "mailto:".
+///
+/// Example:
+///
+/// ```rust
+/// let url = from_relative_url_into_root_url("../img/alfa.png?v=1", "/posts/2026/");
+/// //=> Some("/posts/img/alfa.png?v=1")
+/// ```
+///
+pub fn from_relative_url_into_root_url(url: &str, dir_url: &str) -> Option<String> {
+    static SCHEME_REGEX: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.\-]*:").expect("regex")
+    });
+    if url.is_empty() || url.starts_with(['/', '#', '?']) || SCHEME_REGEX.is_match(url) {
+        return None
+    }
+    let (path, rest) = url.split_at(url.find(['?', '#']).unwrap_or(url.len()));
+    let mut parts: Vec<&str> = dir_url.split('/').filter(|x| !x.is_empty()).collect();
+    let segments: Vec<&str> = path.split('/').collect();
+    for (i, segment) in segments.iter().enumerate() {
+        match *segment {
+            ".." => { parts.pop(); },
+            "." => {},
+            "" if i + 1 < segments.len() => {},
+            x => parts.push(x),
+        }
+    }
+    let trailing_slash = matches!(segments.last(), Some(&"") | Some(&".") | Some(&".."));
+    let mut s = format!("/{}", parts.join("/"));
+    if trailing_slash && !s.ends_with('/') { s.push('/'); }
+    s.push_str(rest);
+    Some(s)
+}
+
+/// Rewrite links, such as "http:" into "https:", and each Markdown link
+/// into its page URL via the permalinks, or else into its output link,
+/// such as ".html", from the page's input directory URL, such as "/posts/".
+pub fn rewrite(s: &str, dir_url: &str, permalinks: &Map<String, String>, extension: &Path) -> String {
     let element_content_handlers = vec![
         element!("a[href]", |e| {
             crate::rewriting::lol::rewrite_href_from_http_to_https(e);
-            crate::rewriting::lol::rewrite_href_from_md_to_html(e);
+            crate::rewriting::lol::rewrite_href_from_md_to_permalink(e, dir_url, permalinks);
+            crate::rewriting::lol::rewrite_href_from_md_to_html(e, extension);
              Ok(())
         })
     ];
@@ -35,6 +127,41 @@ pub fn rewrite(s: &str) -> String {
     ).unwrap()
 }
 
+/// Rewrite like `rewrite`, plus rewrite each relative link and asset path
+/// into a root-relative one, from the page's directory URL, such as "/".
+///
+/// This is for a page that a host serves from any URL depth,
+/// such as the not found page "404.html".
+pub fn rewrite_with_root_links(s: &str, dir_url: &str, permalinks: &Map<String, String>, extension: &Path) -> String {
+    let element_content_handlers = vec![
+        element!("a[href]", |e| {
+            crate::rewriting::lol::rewrite_href_from_http_to_https(e);
+            crate::rewriting::lol::rewrite_href_from_md_to_permalink(e, dir_url, permalinks);
+            crate::rewriting::lol::rewrite_href_from_md_to_html(e, extension);
+            crate::rewriting::lol::rewrite_link_from_relative_to_root(e, "href", dir_url);
+            Ok(())
+        }),
+        element!("link[href]", |e| {
+            crate::rewriting::lol::rewrite_link_from_relative_to_root(e, "href", dir_url);
+            Ok(())
+        }),
+        element!("img[src], script[src], source[src], iframe[src], audio[src], video[src]", |e| {
+            crate::rewriting::lol::rewrite_link_from_relative_to_root(e, "src", dir_url);
+            Ok(())
+        }),
+        element!("form[action]", |e| {
+            crate::rewriting::lol::rewrite_link_from_relative_to_root(e, "action", dir_url);
+            Ok(())
+        }),
+    ];
+    rewrite_str(s,
+        RewriteStrSettings {
+            element_content_handlers,
+            ..RewriteStrSettings::default()
+        }
+    ).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,29 +170,69 @@ mod tests {
     fn test_rewrite_from_md_to_html_x_success() {
         let input = r#"<a href="foo.md">"#;
         let expect =  r#"<a href="foo.html">"#;
-        let actual = rewrite(input);
+        let actual = rewrite(input, "/", &Map::new(), Path::new("html"));
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_rewrite_from_md_to_html_x_extension() {
+        let input = r#"<a href="foo.md">"#;
+        let expect =  r#"<a href="foo.htm">"#;
+        let actual = rewrite(input, "/", &Map::new(), Path::new("htm"));
         assert_eq!(actual, expect);
     }
 
     #[test]
     fn test_rewrite_from_md_to_html_x_skip_because_not_ends_with() {
         let input = r#"<a href="foo.md/goo">"#;
-        let actual = rewrite(input);
+        let actual = rewrite(input, "/", &Map::new(), Path::new("html"));
         assert_eq!(actual, input);
     }
 
+    #[test]
+    fn test_from_relative_url_into_root_url() {
+        assert_eq!(from_relative_url_into_root_url("alfa.html", "/"), Some(String::from("/alfa.html")));
+        assert_eq!(from_relative_url_into_root_url("./img/alfa.png", "/"), Some(String::from("/img/alfa.png")));
+        assert_eq!(from_relative_url_into_root_url("../img/alfa.png?v=1#x", "/posts/2026/"), Some(String::from("/posts/img/alfa.png?v=1#x")));
+        assert_eq!(from_relative_url_into_root_url("../../..", "/posts/"), Some(String::from("/")));
+        assert_eq!(from_relative_url_into_root_url("posts/", "/"), Some(String::from("/posts/")));
+        assert_eq!(from_relative_url_into_root_url("/alfa.html", "/"), None);
+        assert_eq!(from_relative_url_into_root_url("#top", "/"), None);
+        assert_eq!(from_relative_url_into_root_url("https://example.com", "/"), None);
+        assert_eq!(from_relative_url_into_root_url("mailto:alfa@example.com", "/"), None);
+        assert_eq!(from_relative_url_into_root_url("//example.com/alfa.png", "/"), None);
+    }
+
+    #[test]
+    fn test_rewrite_with_root_links() {
+        let input = r##"<a href="posts/alfa.md">x</a><a href="#top">y</a><img src="img/bravo.png"><link href="style.css" rel="stylesheet"><script src="/app.js"></script>"##;
+        let expect = r##"<a href="/posts/alfa.html">x</a><a href="#top">y</a><img src="/img/bravo.png"><link href="/style.css" rel="stylesheet"><script src="/app.js"></script>"##;
+        assert_eq!(rewrite_with_root_links(input, "/", &Map::new(), Path::new("html")), expect);
+    }
+
+    #[test]
+    fn test_rewrite_from_md_to_permalink() {
+        let permalinks = map!(
+            String::from("/posts/alfa.md") => String::from("/blog/2026/alfa/"),
+            String::from("/about.md") => String::from("/about/")
+        );
+        let input = r#"<a href="alfa.md#top">x</a><a href="../about.md">y</a><a href="/posts/alfa.md">z</a><a href="bravo.md">w</a>"#;
+        let expect = r#"<a href="/blog/2026/alfa/#top">x</a><a href="/about/">y</a><a href="/blog/2026/alfa/">z</a><a href="bravo.html">w</a>"#;
+        assert_eq!(rewrite(input, "/posts/", &permalinks, Path::new("html")), expect);
+    }
+
     #[test]
     fn test_rewrite_from_http_to_https_x_success() {
         let input = r#"<a href="http://example.com">"#;
         let expect =  r#"<a href="https://example.com">"#;
-        let actual = rewrite(input);
+        let actual = rewrite(input, "/", &Map::new(), Path::new("html"));
         assert_eq!(actual, expect);
     }
 
     #[test]
     fn test_rewrite_from_http_to_https_x_skip_because_not_starts_with() {
         let input = r#"<a href="foo/http:/goo">"#;
-        let actual = rewrite(input);
+        let actual = rewrite(input, "/", &Map::new(), Path::new("html"));
         assert_eq!(actual, input);
     }
 
@@ -0,0 +1,239 @@
+//! Schema declaration for the front matter of pages in a content directory.
+//!
+//! A content directory can declare its schema in a data file named
+//! `_schema.json`, `_schema.toml`, `_schema.yaml`, or `_schema.yml`.
+//!
+//! Example `posts/_schema.yaml`:
+//!
+//! ```yaml
+//! title: string
+//! date: date
+//! tags: [string]
+//! subtitle: string?
+//! ```
+//!
+//! The declaration applies to the pages in its directory and in its
+//! subdirectories, unless a subdirectory declares its own schema.
+//!
+//! The declaration vets each page's front matter plus its defaults,
+//! before any computed variables, so a `title` from the first headline
+//! doesn't satisfy `title: string`.
+
+use std::path::PathBuf;
+use crate::types::{list::*, map::*};
+use crate::state::state_enum::StateEnum;
+use crate::schema::schema_field::SchemaField;
+use crate::schema::schema_violation::SchemaViolation;
+use crate::f::from_data_path_buf_into_state::*;
+
+/// The file stem of a schema declaration file.
+pub const SCHEMA_FILE_STEM: &str = "_schema";
+
+/// The minimum similarity of two keys to hint at a misspelling.
+const HINT_SIMILARITY_MIN: f64 = 0.8;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDeclaration {
+    pub fields: Map<String, SchemaField>,
+}
+
+impl SchemaDeclaration {
+
+    /// Load the schema declaration file in a directory, if any.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let dir = PathBuf::from("posts");
+    /// let schema: Option<SchemaDeclaration> = SchemaDeclaration::load_from_dir(&dir).unwrap();
+    /// ```
+    ///
+    pub fn load_from_dir(dir: &PathBuf) -> Result<Option<Self>, Error> {
+        trace!("SchemaDeclaration::load_from_dir ➡ dir: {:?}", dir);
+        match find_data_path_buf_in_dir_by_file_stem(dir, SCHEMA_FILE_STEM) {
+            Some(path_buf) => Ok(Some(Self::load_from_path_buf(&path_buf)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Load a schema declaration from a data file.
+    pub fn load_from_path_buf(path_buf: &PathBuf) -> Result<Self, Error> {
+        trace!("SchemaDeclaration::load_from_path_buf ➡ path_buf: {:?}", path_buf);
+        let state = from_data_path_buf_into_state(path_buf)
+        .map_or_else(
+            |err| Err(Error::Load(err)),
+            Ok
+        )?;
+        Self::from_state_enum(&state.to_state_enum())
+        .map_or_else(
+            |err| Err(Error::Declaration { path_buf: path_buf.to_owned(), err: Box::new(err) }),
+            Ok
+        )
+    }
+
+    /// Create a schema declaration from a state of field names and types.
+    pub fn from_state_enum(state_enum: &StateEnum) -> Result<Self, Error> {
+        let value = serde_json::to_value(state_enum)
+        .map_or_else(
+            |err| Err(Error::Serialize(err)),
+            Ok
+        )?;
+        let mut fields: Map<String, SchemaField> = Map::new();
+        if let serde_json::Value::Object(object) = value {
+            for (key, value) in object.iter() {
+                let field = SchemaField::parse(value)
+                .map_or_else(
+                    |err| Err(Error::Field { key: key.to_owned(), err }),
+                    Ok
+                )?;
+                fields.insert(key.to_owned(), field);
+            }
+        }
+        Ok(SchemaDeclaration { fields })
+    }
+
+    /// Is the given file name reserved for a schema declaration?
+    pub fn is_schema_file_name(file_name: &str) -> bool {
+        DATA_FILE_NAME_EXTENSIONS.iter().any(|extension|
+            file_name == format!("{}.{}", SCHEMA_FILE_STEM, extension)
+        )
+    }
+
+    /// Validate a page's state, and return every violation.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let violations = schema.validate(&input, &state.to_state_enum());
+    /// for violation in violations { error!("{}", violation); }
+    /// ```
+    ///
+    pub fn validate(&self, input: &PathBuf, state_enum: &StateEnum) -> List<SchemaViolation> {
+        trace!("SchemaDeclaration::validate ➡ input: {:?}", input);
+        let value = serde_json::to_value(state_enum).unwrap_or(serde_json::Value::Null);
+        let empty = serde_json::Map::new();
+        let object = value.as_object().unwrap_or(&empty);
+        let mut violations: List<SchemaViolation> = List::new();
+        for (key, field) in self.fields.iter() {
+            match object.get(key) {
+                Some(value) if !value.is_null() => {
+                    if !field.schema_type.is_match(value) {
+                        violations.push(SchemaViolation::Mismatch {
+                            input: input.to_owned(),
+                            key: key.to_owned(),
+                            expect: field.schema_type.clone(),
+                            actual: value.to_string(),
+                        });
+                    }
+                },
+                _ => {
+                    if field.required {
+                        violations.push(SchemaViolation::Missing {
+                            input: input.to_owned(),
+                            key: key.to_owned(),
+                            expect: field.schema_type.clone(),
+                            hint: self.hint(key, object),
+                        });
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    /// Find an undeclared key that looks like a misspelling of the given key.
+    fn hint(&self, key: &str, object: &serde_json::Map<String, serde_json::Value>) -> Option<String> {
+        object.keys()
+        .filter(|x| !self.fields.contains_key(*x))
+        .map(|x| (strsim::jaro_winkler(key, x), x))
+        .filter(|(similarity, _)| *similarity >= HINT_SIMILARITY_MIN)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, x)| x.to_owned())
+    }
+
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+
+    #[error("Load ➡ {0:?}")]
+    Load(crate::f::from_data_path_buf_into_state::Error),
+
+    #[error("Serialize ➡ {0:?}")]
+    Serialize(serde_json::Error),
+
+    #[error("Field ➡ key: {key:?}, err: {err:?}")]
+    Field {
+        key: String,
+        err: crate::schema::schema_field::Error,
+    },
+
+    #[error("Declaration ➡ path_buf: {path_buf:?}, err: {err:?}")]
+    Declaration {
+        path_buf: PathBuf,
+        err: Box<Error>,
+    },
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use crate::schema::schema_type::SchemaType;
+
+    pub static DIR: Lazy<PathBuf> = Lazy::new(||
+        crate::testing::TESTS_DIR
+        .join("src")
+        .join("schema")
+        .join("schema_declaration")
+    );
+
+    fn state_enum_from_yaml(s: &str) -> StateEnum {
+        StateEnum::StateWithYAML(serde_yaml::from_str(s).expect("from_str"))
+    }
+
+    #[test]
+    fn test_load_from_dir_x_present() {
+        let schema = SchemaDeclaration::load_from_dir(&DIR.join("present")).expect("load").expect("some");
+        assert_eq!(schema.fields.len(), 4);
+        assert_eq!(schema.fields["tags"].schema_type, SchemaType::List(Box::new(SchemaType::String)));
+        assert!(!schema.fields["subtitle"].required);
+    }
+
+    #[test]
+    fn test_load_from_dir_x_absent() {
+        let schema = SchemaDeclaration::load_from_dir(&DIR.join("absent")).expect("load");
+        assert!(schema.is_none());
+    }
+
+    #[test]
+    fn test_is_schema_file_name() {
+        assert!(SchemaDeclaration::is_schema_file_name("_schema.yaml"));
+        assert!(SchemaDeclaration::is_schema_file_name("_schema.json"));
+        assert!(!SchemaDeclaration::is_schema_file_name("_schema.md"));
+        assert!(!SchemaDeclaration::is_schema_file_name("schema.yaml"));
+    }
+
+    #[test]
+    fn test_validate_x_ok() {
+        let schema = SchemaDeclaration::load_from_dir(&DIR.join("present")).expect("load").expect("some");
+        let state_enum = state_enum_from_yaml("title: alfa\ndate: 2026-10-18\ntags: [bravo, charlie]\n");
+        let violations = schema.validate(&PathBuf::from("alfa.md"), &state_enum);
+        assert!(violations.is_empty(), "violations: {:?}", violations);
+    }
+
+    #[test]
+    fn test_validate_x_violations() {
+        let schema = SchemaDeclaration::load_from_dir(&DIR.join("present")).expect("load").expect("some");
+        let state_enum = state_enum_from_yaml("titel: alfa\ndate: yesterday\ntags: bravo\n");
+        let input = PathBuf::from("alfa.md");
+        let violations = schema.validate(&input, &state_enum);
+        assert_eq!(violations, vec![
+            SchemaViolation::Mismatch { input: input.clone(), key: String::from("date"), expect: SchemaType::Date, actual: String::from("\"yesterday\"") },
+            SchemaViolation::Mismatch { input: input.clone(), key: String::from("tags"), expect: SchemaType::List(Box::new(SchemaType::String)), actual: String::from("\"bravo\"") },
+            SchemaViolation::Missing { input: input.clone(), key: String::from("title"), expect: SchemaType::String, hint: Some(String::from("titel")) },
+        ]);
+    }
+
+}
@@ -0,0 +1,95 @@
+//! Schema field for a front matter key.
+//!
+//! A field is required by default. A field is optional when its
+//! declaration ends with a question mark, such as "string?".
+
+use crate::schema::schema_type::SchemaType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaField {
+    pub schema_type: SchemaType,
+    pub required: bool,
+}
+
+impl SchemaField {
+
+    /// Parse a schema field from its declaration value.
+    ///
+    /// The declaration can be a text, such as "date" or "[string]" or "int?",
+    /// or can be a one-item list, such as YAML `[string]`, for a list type.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let field = SchemaField::parse(&serde_json::json!("string?")).unwrap();
+    /// assert_eq!(field.schema_type, SchemaType::String);
+    /// assert_eq!(field.required, false);
+    /// ```
+    ///
+    pub fn parse(value: &serde_json::Value) -> Result<Self, Error> {
+        match value {
+            serde_json::Value::String(s) => {
+                let (text, required) = match s.trim().strip_suffix('?') {
+                    Some(x) => (x, false),
+                    None => (s.as_str(), true),
+                };
+                let schema_type = SchemaType::parse(text)
+                .map_or_else(
+                    |err| Err(Error::SchemaType(err)),
+                    Ok
+                )?;
+                Ok(SchemaField { schema_type, required })
+            },
+            serde_json::Value::Array(items) if items.len() == 1 => {
+                let inner = SchemaField::parse(&items[0])?;
+                Ok(SchemaField { schema_type: SchemaType::List(Box::new(inner.schema_type)), required: true })
+            },
+            _ => Err(Error::Declaration { value: value.to_owned() }),
+        }
+    }
+
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+
+    #[error("SchemaType ➡ {0:?}")]
+    SchemaType(crate::schema::schema_type::Error),
+
+    #[error("Declaration ➡ value: {value:?}")]
+    Declaration {
+        value: serde_json::Value,
+    },
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_x_required() {
+        let actual = SchemaField::parse(&json!("date")).unwrap();
+        assert_eq!(actual, SchemaField { schema_type: SchemaType::Date, required: true });
+    }
+
+    #[test]
+    fn test_parse_x_optional() {
+        let actual = SchemaField::parse(&json!("[string]?")).unwrap();
+        assert_eq!(actual, SchemaField { schema_type: SchemaType::List(Box::new(SchemaType::String)), required: false });
+    }
+
+    #[test]
+    fn test_parse_x_list_via_array() {
+        let actual = SchemaField::parse(&json!(["string"])).unwrap();
+        assert_eq!(actual, SchemaField { schema_type: SchemaType::List(Box::new(SchemaType::String)), required: true });
+    }
+
+    #[test]
+    fn test_parse_x_err() {
+        assert!(SchemaField::parse(&json!(1)).is_err());
+        assert!(SchemaField::parse(&json!("strnig")).is_err());
+    }
+
+}
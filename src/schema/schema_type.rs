@@ -0,0 +1,185 @@
+//! Schema type for a front matter field.
+//!
+//! A schema type is declared as a short text, such as "string" or "date",
+//! and a list is declared with brackets, such as "[string]".
+//!
+//! Matching is lenient for string values, because some matter flavors,
+//! such as HTML comments and Markdown comments, produce only strings.
+//! For example the string "3" matches the type "int".
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// TOML serializes a datetime as a struct with this private key.
+const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaType {
+    Any,
+    Bool,
+    Date,
+    Float,
+    Int,
+    List(Box<SchemaType>),
+    Map,
+    String,
+}
+
+impl SchemaType {
+
+    /// Parse a schema type from its declaration text.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let schema_type = SchemaType::parse("[string]").unwrap();
+    /// assert_eq!(schema_type, SchemaType::List(Box::new(SchemaType::String)));
+    /// ```
+    ///
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let s = s.trim();
+        if let Some(inner) = s.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+            return Ok(SchemaType::List(Box::new(SchemaType::parse(inner)?)))
+        }
+        match s.to_lowercase().as_str() {
+            "any" => Ok(SchemaType::Any),
+            "bool" | "boolean" => Ok(SchemaType::Bool),
+            "date" | "datetime" => Ok(SchemaType::Date),
+            "float" | "number" => Ok(SchemaType::Float),
+            "int" | "integer" => Ok(SchemaType::Int),
+            "list" | "array" => Ok(SchemaType::List(Box::new(SchemaType::Any))),
+            "map" | "object" | "table" => Ok(SchemaType::Map),
+            "string" | "str" | "text" => Ok(SchemaType::String),
+            _ => Err(Error::Unknown { text: s.to_owned() }),
+        }
+    }
+
+    /// Does the value match this schema type?
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let flag = SchemaType::Int.is_match(&serde_json::json!(3));
+    /// assert!(flag);
+    /// ```
+    ///
+    pub fn is_match(&self, value: &serde_json::Value) -> bool {
+        use serde_json::Value;
+        match (self, value) {
+            (_, Value::Null) => false,
+            (SchemaType::Any, _) => true,
+            (SchemaType::Bool, Value::Bool(_)) => true,
+            (SchemaType::Bool, Value::String(s)) => s == "true" || s == "false",
+            (SchemaType::Date, Value::String(s)) => is_date_str(s),
+            (SchemaType::Date, Value::Object(o)) => o.get(TOML_DATETIME_KEY).and_then(Value::as_str).is_some_and(is_date_str),
+            (SchemaType::Float, Value::Number(_)) => true,
+            (SchemaType::Float, Value::String(s)) => s.trim().parse::<f64>().is_ok(),
+            (SchemaType::Int, Value::Number(n)) => n.is_i64() || n.is_u64(),
+            (SchemaType::Int, Value::String(s)) => s.trim().parse::<i64>().is_ok(),
+            (SchemaType::List(inner), Value::Array(items)) => items.iter().all(|item| inner.is_match(item)),
+            (SchemaType::Map, Value::Object(o)) => !o.contains_key(TOML_DATETIME_KEY),
+            (SchemaType::String, Value::String(_)) => true,
+            _ => false,
+        }
+    }
+
+}
+
+impl std::fmt::Display for SchemaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaType::Any => write!(f, "any"),
+            SchemaType::Bool => write!(f, "bool"),
+            SchemaType::Date => write!(f, "date"),
+            SchemaType::Float => write!(f, "float"),
+            SchemaType::Int => write!(f, "int"),
+            SchemaType::List(inner) => write!(f, "[{}]", inner),
+            SchemaType::Map => write!(f, "map"),
+            SchemaType::String => write!(f, "string"),
+        }
+    }
+}
+
+pub static DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\A(?P<year>\d{4})-(?P<month>\d{2})-(?P<day>\d{2})([T ]\d{2}:\d{2}(:\d{2}(\.\d+)?)?(Z|[+-]\d{2}:?\d{2})?)?\z").unwrap()
+});
+
+/// Is the text an ISO 8601 date, with an optional time?
+///
+/// Example:
+///
+/// ```rust
+/// assert!(is_date_str("2026-10-18"));
+/// assert!(is_date_str("2026-10-18T12:00:00Z"));
+/// ```
+///
+fn is_date_str(s: &str) -> bool {
+    if let Some(captures) = DATE_REGEX.captures(s.trim()) {
+        let month: u32 = captures["month"].parse().unwrap_or(0);
+        let day: u32 = captures["day"].parse().unwrap_or(0);
+        return (1..=12).contains(&month) && (1..=31).contains(&day)
+    }
+    false
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+
+    #[error("Unknown ➡ text: {text:?}")]
+    Unknown {
+        text: String,
+    },
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(SchemaType::parse("string").unwrap(), SchemaType::String);
+        assert_eq!(SchemaType::parse("Date").unwrap(), SchemaType::Date);
+        assert_eq!(SchemaType::parse("[string]").unwrap(), SchemaType::List(Box::new(SchemaType::String)));
+        assert_eq!(SchemaType::parse("[[int]]").unwrap(), SchemaType::List(Box::new(SchemaType::List(Box::new(SchemaType::Int)))));
+        assert!(SchemaType::parse("strnig").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(SchemaType::parse("[string]").unwrap().to_string(), "[string]");
+    }
+
+    #[test]
+    fn test_is_match_x_scalar() {
+        assert!(SchemaType::String.is_match(&json!("alfa")));
+        assert!(!SchemaType::String.is_match(&json!(1)));
+        assert!(SchemaType::Int.is_match(&json!(1)));
+        assert!(SchemaType::Int.is_match(&json!("1")));
+        assert!(!SchemaType::Int.is_match(&json!(1.5)));
+        assert!(SchemaType::Float.is_match(&json!(1.5)));
+        assert!(SchemaType::Bool.is_match(&json!(true)));
+        assert!(SchemaType::Bool.is_match(&json!("false")));
+        assert!(!SchemaType::Any.is_match(&json!(null)));
+    }
+
+    #[test]
+    fn test_is_match_x_date() {
+        assert!(SchemaType::Date.is_match(&json!("2026-10-18")));
+        assert!(SchemaType::Date.is_match(&json!("2026-10-18T12:34:56Z")));
+        assert!(SchemaType::Date.is_match(&json!({"$__toml_private_datetime": "2026-10-18"})));
+        assert!(!SchemaType::Date.is_match(&json!("2026-13-18")));
+        assert!(!SchemaType::Date.is_match(&json!("yesterday")));
+    }
+
+    #[test]
+    fn test_is_match_x_list() {
+        let schema_type = SchemaType::parse("[string]").unwrap();
+        assert!(schema_type.is_match(&json!(["alfa", "bravo"])));
+        assert!(schema_type.is_match(&json!([])));
+        assert!(!schema_type.is_match(&json!(["alfa", 1])));
+        assert!(!schema_type.is_match(&json!("alfa")));
+    }
+
+}
@@ -0,0 +1,67 @@
+//! Schema violation, i.e. one problem with one page's front matter.
+
+use std::path::PathBuf;
+use crate::schema::schema_type::SchemaType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaViolation {
+
+    /// The page is missing a required key.
+    /// The hint is a similar key that the page has, which may be a misspelling.
+    Missing {
+        input: PathBuf,
+        key: String,
+        expect: SchemaType,
+        hint: Option<String>,
+    },
+
+    /// The page has the key, yet its value doesn't match the declared type.
+    Mismatch {
+        input: PathBuf,
+        key: String,
+        expect: SchemaType,
+        actual: String,
+    },
+
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaViolation::Missing { input, key, expect, hint: Some(hint) } =>
+                write!(f, "{:?} ➡ missing key {:?} of type {}; did you mean {:?}?", input, key, expect, hint),
+            SchemaViolation::Missing { input, key, expect, hint: None } =>
+                write!(f, "{:?} ➡ missing key {:?} of type {}", input, key, expect),
+            SchemaViolation::Mismatch { input, key, expect, actual } =>
+                write!(f, "{:?} ➡ key {:?} must be type {}, but is {}", input, key, expect, actual),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_x_missing() {
+        let violation = SchemaViolation::Missing {
+            input: PathBuf::from("alfa.md"),
+            key: String::from("title"),
+            expect: SchemaType::String,
+            hint: Some(String::from("titel")),
+        };
+        assert_eq!(violation.to_string(), r#""alfa.md" ➡ missing key "title" of type string; did you mean "titel"?"#);
+    }
+
+    #[test]
+    fn test_display_x_mismatch() {
+        let violation = SchemaViolation::Mismatch {
+            input: PathBuf::from("alfa.md"),
+            key: String::from("date"),
+            expect: SchemaType::Date,
+            actual: String::from("\"yesterday\""),
+        };
+        assert_eq!(violation.to_string(), r#""alfa.md" ➡ key "date" must be type date, but is "yesterday""#);
+    }
+
+}
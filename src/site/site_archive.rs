@@ -0,0 +1,241 @@
+//! Site archive, i.e. a way to group dated pages by year and by month.
+//!
+//! A page is in the archive when its front matter `date` is a date,
+//! such as "2026-10-18" or "2026-10-18T12:00:00Z"; its year and month
+//! are in UTC.
+//!
+//! The archive gets generated listing pages:
+//!
+//! * An archive index page, `/archive/`, with the variable `archive`
+//!   that has `title`, `url`, and `years`, each with its `months`.
+//!
+//! * A year page per year, such as `/archive/2026/`, with the variable `year`
+//!   that has `year`, `title`, `url`, `count`, `months`, and `pages`.
+//!
+//! * A month page per month, such as `/archive/2026/10/`, with the variable
+//!   `month` that has `year`, `month`, `title`, `url`, `count`, and `pages`.
+//!
+//! Years and months are newest first, and pages are sorted by date, newest first.
+
+use std::path::{Path, PathBuf};
+use crate::types::list::*;
+use crate::state::state_value::StateValue;
+use crate::f::from_date_str_into_system_time::*;
+use crate::f::from_relative_path_into_url_string::*;
+use crate::f::from_system_time_into_rfc3339_string::*;
+
+/// The archive directory name, such as "/archive/2026/".
+pub const ARCHIVE_PATH_AS_STR: &str = "archive";
+
+/// The archive index page title.
+pub const ARCHIVE_TITLE_DEFAULT: &str = "Archive";
+
+/// The month names, for month titles such as "October 2026".
+pub const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SiteArchive {
+
+    /// The years, newest first.
+    pub years: List<SiteArchiveYear>,
+
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteArchiveYear {
+
+    /// The year as four digits, such as "2026".
+    pub year: String,
+
+    /// The months, newest first.
+    pub months: List<SiteArchiveMonth>,
+
+    /// The indexes of the year's pages in `SiteModel::pages`, in walk order.
+    pub page_indexes: List<usize>,
+
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteArchiveMonth {
+
+    /// The month as two digits, such as "10".
+    pub month: String,
+
+    /// The indexes of the month's pages in `SiteModel::pages`, in walk order.
+    pub page_indexes: List<usize>,
+
+}
+
+impl SiteArchive {
+
+    /// Get the output path relative to the output directory, i.e. "archive/index.html".
+    pub fn output_path(&self, extension: &Path) -> PathBuf {
+        PathBuf::from(ARCHIVE_PATH_AS_STR).join(INDEX_FILE_NAME_AS_STR).with_extension(extension)
+    }
+
+    /// Get the root-relative URL, i.e. "/archive/".
+    pub fn url(&self) -> String {
+        from_relative_path_into_url_string(&PathBuf::from(ARCHIVE_PATH_AS_STR).join(INDEX_FILE_NAME_AS_STR))
+    }
+
+    /// Get the year output path relative to the output directory, such as "archive/2026/index.html".
+    pub fn year_output_path(&self, year: &SiteArchiveYear, extension: &Path) -> PathBuf {
+        PathBuf::from(ARCHIVE_PATH_AS_STR).join(&year.year).join(INDEX_FILE_NAME_AS_STR).with_extension(extension)
+    }
+
+    /// Get the year root-relative URL, such as "/archive/2026/".
+    pub fn year_url(&self, year: &SiteArchiveYear) -> String {
+        from_relative_path_into_url_string(&PathBuf::from(ARCHIVE_PATH_AS_STR).join(&year.year).join(INDEX_FILE_NAME_AS_STR))
+    }
+
+    /// Get the month output path relative to the output directory, such as "archive/2026/10/index.html".
+    pub fn month_output_path(&self, year: &SiteArchiveYear, month: &SiteArchiveMonth, extension: &Path) -> PathBuf {
+        PathBuf::from(ARCHIVE_PATH_AS_STR).join(&year.year).join(&month.month).join(INDEX_FILE_NAME_AS_STR).with_extension(extension)
+    }
+
+    /// Get the month root-relative URL, such as "/archive/2026/10/".
+    pub fn month_url(&self, year: &SiteArchiveYear, month: &SiteArchiveMonth) -> String {
+        from_relative_path_into_url_string(&PathBuf::from(ARCHIVE_PATH_AS_STR).join(&year.year).join(&month.month).join(INDEX_FILE_NAME_AS_STR))
+    }
+
+    /// Convert to a state value, with each year's link and its month links.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let value = archive.to_state_value();
+    /// //=> {"title": "Archive", "url": "/archive/", "years": [{"year": "2026", …, "months": [{"month": "10", …}]}]}
+    /// ```
+    ///
+    pub fn to_state_value(&self) -> StateValue {
+        StateValue::Map(map!(
+            String::from("title") => StateValue::from(ARCHIVE_TITLE_DEFAULT),
+            String::from("url") => StateValue::from(self.url()),
+            String::from("years") => StateValue::List(self.years.iter().map(|year| {
+                let mut value = self.year_link_state_value(year);
+                value.deep_merge(StateValue::Map(map!(
+                    String::from("months") => StateValue::List(year.months.iter().map(|x| self.month_link_state_value(year, x)).collect())
+                )));
+                value
+            }).collect())
+        ))
+    }
+
+    /// Convert a year to a state value link, with its year, title, URL, and page count.
+    pub fn year_link_state_value(&self, year: &SiteArchiveYear) -> StateValue {
+        StateValue::Map(map!(
+            String::from("year") => StateValue::from(year.year.as_str()),
+            String::from("title") => StateValue::from(year.year.as_str()),
+            String::from("url") => StateValue::from(self.year_url(year)),
+            String::from("count") => StateValue::Int(year.page_indexes.len() as i64)
+        ))
+    }
+
+    /// Convert a month to a state value link, with its year, month, title, URL, and page count.
+    pub fn month_link_state_value(&self, year: &SiteArchiveYear, month: &SiteArchiveMonth) -> StateValue {
+        StateValue::Map(map!(
+            String::from("year") => StateValue::from(year.year.as_str()),
+            String::from("month") => StateValue::from(month.month.as_str()),
+            String::from("title") => StateValue::from(month.title(year)),
+            String::from("url") => StateValue::from(self.month_url(year, month)),
+            String::from("count") => StateValue::Int(month.page_indexes.len() as i64)
+        ))
+    }
+
+}
+
+impl SiteArchiveMonth {
+
+    /// Get the title, such as "October 2026".
+    pub fn title(&self, year: &SiteArchiveYear) -> String {
+        let name = self.month.parse::<usize>().ok()
+            .and_then(|x| x.checked_sub(1))
+            .and_then(|x| MONTH_NAMES.get(x))
+            .copied()
+            .unwrap_or(self.month.as_str());
+        format!("{} {}", name, year.year)
+    }
+
+}
+
+/// Build an archive from each page's front matter date, if any, in walk order.
+///
+/// Example:
+///
+/// ```rust
+/// let archive = from_values_into_site_archive(&[Some(StateValue::from("2026-10-18")), None]);
+/// //=> SiteArchive { years: [SiteArchiveYear { year: "2026", months: [SiteArchiveMonth { month: "10", page_indexes: [0] }], page_indexes: [0] }] }
+/// ```
+///
+pub fn from_values_into_site_archive(values: &[Option<StateValue>]) -> SiteArchive {
+    let mut years: List<SiteArchiveYear> = List::new();
+    for (index, value) in values.iter().enumerate() {
+        let Some(time) = value.as_ref().and_then(|x| from_date_str_into_system_time(&x.to_plain_string())) else { continue };
+        let s = from_system_time_into_rfc3339_string(time);
+        let (year_str, month_str) = (&s[0..4], &s[5..7]);
+        let year = match years.iter().position(|x| x.year == year_str) {
+            Some(i) => &mut years[i],
+            None => {
+                years.push(SiteArchiveYear { year: String::from(year_str), months: List::new(), page_indexes: List::new() });
+                years.last_mut().expect("last")
+            }
+        };
+        year.page_indexes.push(index);
+        match year.months.iter_mut().find(|x| x.month == month_str) {
+            Some(month) => month.page_indexes.push(index),
+            None => year.months.push(SiteArchiveMonth { month: String::from(month_str), page_indexes: vec![index] }),
+        }
+    }
+    years.sort_by(|a, b| b.year.cmp(&a.year));
+    for year in years.iter_mut() {
+        year.months.sort_by(|a, b| b.month.cmp(&a.month));
+    }
+    SiteArchive { years }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_values_into_site_archive() {
+        let values = [
+            Some(StateValue::from("2025-12-31")),
+            None,
+            Some(StateValue::from("2026-01-02T03:04:05Z")),
+            Some(StateValue::from("alfa")),
+            Some(StateValue::from("2026-10-18")),
+            Some(StateValue::from("2026-01-20")),
+        ];
+        let archive = from_values_into_site_archive(&values);
+        assert_eq!(archive.years, vec![
+            SiteArchiveYear { year: String::from("2026"), page_indexes: vec![2, 4, 5], months: vec![
+                SiteArchiveMonth { month: String::from("10"), page_indexes: vec![4] },
+                SiteArchiveMonth { month: String::from("01"), page_indexes: vec![2, 5] },
+            ]},
+            SiteArchiveYear { year: String::from("2025"), page_indexes: vec![0], months: vec![
+                SiteArchiveMonth { month: String::from("12"), page_indexes: vec![0] },
+            ]},
+        ]);
+        assert_eq!(archive.month_url(&archive.years[0], &archive.years[0].months[1]), "/archive/2026/01/");
+        assert_eq!(archive.month_output_path(&archive.years[0], &archive.years[0].months[1], Path::new("html")), PathBuf::from("archive/2026/01/index.html"));
+        assert_eq!(archive.to_state_value(), StateValue::from(json!({
+            "title": "Archive",
+            "url": "/archive/",
+            "years": [
+                {"year": "2026", "title": "2026", "url": "/archive/2026/", "count": 3, "months": [
+                    {"year": "2026", "month": "10", "title": "October 2026", "url": "/archive/2026/10/", "count": 1},
+                    {"year": "2026", "month": "01", "title": "January 2026", "url": "/archive/2026/01/", "count": 2},
+                ]},
+                {"year": "2025", "title": "2025", "url": "/archive/2025/", "count": 1, "months": [
+                    {"year": "2025", "month": "12", "title": "December 2025", "url": "/archive/2025/12/", "count": 1},
+                ]},
+            ],
+        })));
+    }
+
+}
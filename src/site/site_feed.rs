@@ -0,0 +1,293 @@
+//! Site feed, i.e. the newest dated pages for feed readers.
+//!
+//! Each feed is written in three formats, in its directory:
+//!
+//! * `feed.xml`: Atom 1.0.
+//!
+//! * `rss.xml`: RSS 2.0.
+//!
+//! * `feed.json`: JSON Feed 1.1.
+//!
+//! Each feed entry comes from a page that has a front matter `date`,
+//! with its `title`, `url`, `summary`, and content HTML.
+//! A feed reader needs absolute links, so a build with feeds
+//! should set a base URL, such as "https://example.com".
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use crate::types::list::*;
+use crate::site::site_page::SitePage;
+use crate::f::from_base_url_and_url_into_absolute_url_string::*;
+use crate::f::from_date_str_into_system_time::*;
+use crate::f::from_relative_path_into_url_string::*;
+use crate::f::from_system_time_into_rfc3339_string::*;
+
+/// The Atom feed file name.
+pub const FEED_ATOM_FILE_NAME_AS_STR: &str = "feed.xml";
+
+/// The RSS feed file name.
+pub const FEED_RSS_FILE_NAME_AS_STR: &str = "rss.xml";
+
+/// The JSON Feed file name.
+pub const FEED_JSON_FILE_NAME_AS_STR: &str = "feed.json";
+
+/// The default number of entries per feed.
+pub const FEED_LENGTH_DEFAULT: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteFeed {
+
+    /// The feed title, such as the site title or the section title.
+    pub title: String,
+
+    /// The directory path relative to the output directory, such as "posts".
+    pub path: PathBuf,
+
+    /// The entries, newest first.
+    pub entries: List<SiteFeedEntry>,
+
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteFeedEntry {
+
+    /// The page title.
+    pub title: String,
+
+    /// The page root-relative URL, such as "/posts/alfa.html".
+    pub url: String,
+
+    /// The page date.
+    pub date: SystemTime,
+
+    /// The page summary as HTML.
+    pub summary: String,
+
+    /// The page content as HTML, before any templating.
+    pub content: String,
+
+}
+
+impl SiteFeed {
+
+    /// Create a feed from pages, with the newest dated pages, up to the length.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let feed = SiteFeed::from_pages("Blog", Path::new("posts"), &pages, 20);
+    /// ```
+    ///
+    pub fn from_pages(title: &str, path: &Path, pages: &[&SitePage], length: usize) -> Self {
+        let mut entries: List<SiteFeedEntry> = pages.iter().filter_map(|page| {
+            let date = page.state.get_value("date")
+                .and_then(|x| from_date_str_into_system_time(&x.to_plain_string()))?;
+            let get = |key: &str| page.state.get_value(key).map(|x| x.to_plain_string()).unwrap_or_default();
+            Some(SiteFeedEntry {
+                title: get("title"),
+                url: get("url"),
+                date,
+                summary: get("summary"),
+                content: page.html.to_owned(),
+            })
+        }).collect();
+        entries.sort_by_key(|x| std::cmp::Reverse(x.date));
+        entries.truncate(length);
+        SiteFeed { title: String::from(title), path: path.to_path_buf(), entries }
+    }
+
+    /// Get the root-relative URL of the feed's home page, such as "/posts/".
+    pub fn url(&self) -> String {
+        from_relative_path_into_url_string(&self.path.join(INDEX_FILE_NAME_AS_STR))
+    }
+
+    /// Get the date of the newest entry, or the epoch when there are no entries.
+    pub fn updated(&self) -> SystemTime {
+        self.entries.first().map_or(UNIX_EPOCH, |x| x.date)
+    }
+
+    /// Convert to an Atom 1.0 XML String.
+    ///
+    /// Each entry has its summary, plus its content when `full_content` is true.
+    pub fn to_atom_string(&self, base_url: Option<&str>, full_content: bool) -> Result<String, quick_xml::Error> {
+        let url = from_base_url_and_url_into_absolute_url_string(base_url, &self.url());
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+        writer.create_element("feed")
+            .with_attribute(("xmlns", "http://www.w3.org/2005/Atom"))
+            .write_inner_content(|writer| {
+                writer.create_element("title").write_text_content(BytesText::new(&self.title))?;
+                writer.create_element("link").with_attribute(("href", url.as_str())).write_empty()?;
+                writer.create_element("link")
+                    .with_attribute(("rel", "self"))
+                    .with_attribute(("href", format!("{}{}", url, FEED_ATOM_FILE_NAME_AS_STR).as_str()))
+                    .write_empty()?;
+                writer.create_element("id").write_text_content(BytesText::new(&url))?;
+                writer.create_element("updated").write_text_content(BytesText::new(&from_system_time_into_rfc3339_string(self.updated())))?;
+                for entry in self.entries.iter() {
+                    let entry_url = from_base_url_and_url_into_absolute_url_string(base_url, &entry.url);
+                    writer.create_element("entry").write_inner_content(|writer| {
+                        writer.create_element("title").write_text_content(BytesText::new(&entry.title))?;
+                        writer.create_element("link").with_attribute(("href", entry_url.as_str())).write_empty()?;
+                        writer.create_element("id").write_text_content(BytesText::new(&entry_url))?;
+                        writer.create_element("updated").write_text_content(BytesText::new(&from_system_time_into_rfc3339_string(entry.date)))?;
+                        writer.create_element("summary").with_attribute(("type", "html")).write_text_content(BytesText::new(&entry.summary))?;
+                        if full_content {
+                            writer.create_element("content").with_attribute(("type", "html")).write_text_content(BytesText::new(&entry.content))?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+    }
+
+    /// Convert to an RSS 2.0 XML String.
+    ///
+    /// Each item description is its summary, or its content when `full_content` is true.
+    pub fn to_rss_string(&self, base_url: Option<&str>, full_content: bool) -> Result<String, quick_xml::Error> {
+        let url = from_base_url_and_url_into_absolute_url_string(base_url, &self.url());
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+        writer.create_element("rss")
+            .with_attribute(("version", "2.0"))
+            .write_inner_content(|writer| {
+                writer.create_element("channel").write_inner_content(|writer| {
+                    writer.create_element("title").write_text_content(BytesText::new(&self.title))?;
+                    writer.create_element("link").write_text_content(BytesText::new(&url))?;
+                    writer.create_element("description").write_text_content(BytesText::new(&self.title))?;
+                    writer.create_element("lastBuildDate").write_text_content(BytesText::new(&httpdate::fmt_http_date(self.updated())))?;
+                    for entry in self.entries.iter() {
+                        let entry_url = from_base_url_and_url_into_absolute_url_string(base_url, &entry.url);
+                        writer.create_element("item").write_inner_content(|writer| {
+                            writer.create_element("title").write_text_content(BytesText::new(&entry.title))?;
+                            writer.create_element("link").write_text_content(BytesText::new(&entry_url))?;
+                            writer.create_element("guid").write_text_content(BytesText::new(&entry_url))?;
+                            writer.create_element("pubDate").write_text_content(BytesText::new(&httpdate::fmt_http_date(entry.date)))?;
+                            let description = if full_content { &entry.content } else { &entry.summary };
+                            writer.create_element("description").write_text_content(BytesText::new(description))?;
+                            Ok(())
+                        })?;
+                    }
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+    }
+
+    /// Convert to a JSON Feed 1.1 String.
+    ///
+    /// Each item `content_html` is its summary, or its content when `full_content` is true.
+    pub fn to_json_string(&self, base_url: Option<&str>, full_content: bool) -> String {
+        let url = from_base_url_and_url_into_absolute_url_string(base_url, &self.url());
+        let items: List<serde_json::Value> = self.entries.iter().map(|entry| {
+            let entry_url = from_base_url_and_url_into_absolute_url_string(base_url, &entry.url);
+            serde_json::json!({
+                "id": entry_url,
+                "url": entry_url,
+                "title": entry.title,
+                "content_html": if full_content { &entry.content } else { &entry.summary },
+                "date_published": from_system_time_into_rfc3339_string(entry.date),
+            })
+        }).collect();
+        let value = serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": self.title,
+            "home_page_url": url,
+            "feed_url": format!("{}{}", url, FEED_JSON_FILE_NAME_AS_STR),
+            "items": items,
+        });
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page(value: serde_json::Value) -> SitePage {
+        SitePage {
+            html: String::from("<p>Alfa &amp; more</p>"),
+            text: String::from("Alfa & more"),
+            ..SitePage::from_json("posts/alfa.md", value)
+        }
+    }
+
+    fn feed() -> SiteFeed {
+        let pages = [
+            page(json!({"title": "Alfa & Bravo", "url": "/posts/alfa.html", "date": "2026-01-02", "summary": "<p>Alfa</p>"})),
+            page(json!({"title": "Charlie", "url": "/posts/charlie.html"})),
+            page(json!({"title": "Delta", "url": "/posts/delta.html", "date": "2026-03-04"})),
+        ];
+        let pages: List<&SitePage> = pages.iter().collect();
+        SiteFeed::from_pages("Blog", Path::new("posts"), &pages, 20)
+    }
+
+    #[test]
+    fn test_from_pages() {
+        let feed = feed();
+        assert_eq!(feed.url(), "/posts/");
+        assert_eq!(feed.entries.iter().map(|x| x.title.as_str()).collect::<List<&str>>(), vec!["Delta", "Alfa & Bravo"]);
+        assert_eq!(feed.updated(), from_date_str_into_system_time("2026-03-04").expect("date"));
+    }
+
+    #[test]
+    fn test_from_pages_x_length() {
+        let pages = [
+            page(json!({"title": "Alfa", "date": "2026-01-02"})),
+            page(json!({"title": "Bravo", "date": "2026-01-03"})),
+        ];
+        let pages: List<&SitePage> = pages.iter().collect();
+        let feed = SiteFeed::from_pages("Blog", Path::new(""), &pages, 1);
+        assert_eq!(feed.entries.iter().map(|x| x.title.as_str()).collect::<List<&str>>(), vec!["Bravo"]);
+    }
+
+    #[test]
+    fn test_to_atom_string() {
+        let s = feed().to_atom_string(Some("https://example.com"), false).expect("atom");
+        assert!(s.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>"));
+        assert!(s.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(s.contains("<link rel=\"self\" href=\"https://example.com/posts/feed.xml\"/>"));
+        assert!(s.contains("<updated>2026-03-04T00:00:00Z</updated>"));
+        assert!(s.contains("<title>Alfa &amp; Bravo</title>"));
+        assert!(s.contains("<id>https://example.com/posts/alfa.html</id>"));
+        assert!(s.contains("<summary type=\"html\">&lt;p&gt;Alfa&lt;/p&gt;</summary>"));
+        assert!(!s.contains("<content"));
+    }
+
+    #[test]
+    fn test_to_atom_string_x_full_content() {
+        let s = feed().to_atom_string(Some("https://example.com"), true).expect("atom");
+        assert!(s.contains("<content type=\"html\">&lt;p&gt;Alfa &amp;amp; more&lt;/p&gt;</content>"));
+    }
+
+    #[test]
+    fn test_to_rss_string() {
+        let s = feed().to_rss_string(Some("https://example.com"), false).expect("rss");
+        assert!(s.contains("<rss version=\"2.0\">"));
+        assert!(s.contains("<link>https://example.com/posts/</link>"));
+        assert!(s.contains("<pubDate>Fri, 02 Jan 2026 00:00:00 GMT</pubDate>"));
+        assert!(s.contains("<description>&lt;p&gt;Alfa&lt;/p&gt;</description>"));
+    }
+
+    #[test]
+    fn test_to_json_string() {
+        let value: serde_json::Value = serde_json::from_str(&feed().to_json_string(Some("https://example.com"), true)).expect("json");
+        assert_eq!(value["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(value["feed_url"], "https://example.com/posts/feed.json");
+        assert_eq!(value["items"][1], json!({
+            "id": "https://example.com/posts/alfa.html",
+            "url": "https://example.com/posts/alfa.html",
+            "title": "Alfa & Bravo",
+            "content_html": "<p>Alfa &amp; more</p>",
+            "date_published": "2026-01-02T00:00:00Z",
+        }));
+    }
+
+}
@@ -0,0 +1,110 @@
+//! Site language, i.e. a language variant of a page, for a multilingual site.
+//!
+//! The option `--language CODE…` lists the site languages, such as
+//! `en fr de es`, and the first is the default language.
+//!
+//! A file name can have a language code before its extension, such as
+//! `about.fr.md`, which is the French variant of `about.md`. A file name
+//! without a language code is in the default language.
+//!
+//! Each language gets its own output root: the default language is at the
+//! output root, such as `about.html`, and any other language is in its
+//! own directory, such as `fr/about.html`.
+//!
+//! Each page gets the variables:
+//!
+//! * `lang`: the language code, such as "fr", which the default template
+//!   puts in `<html lang>`.
+//!
+//! * `translations`: a map of each language variant's code to its URL,
+//!   including this page, such as `{"en": "/about.html", "fr": "/fr/about.html"}`.
+
+use std::path::{Path, PathBuf};
+
+/// Convert from an input relative path and the site languages into the
+/// page's language and its language-rooted path.
+///
+/// Example:
+///
+/// ```rust
+/// let languages = [String::from("en"), String::from("fr")];
+/// from_path_and_languages_into_language_and_path(Path::new("posts/alfa.fr.md"), &languages);
+/// //=> ("fr", "fr/posts/alfa.md")
+/// from_path_and_languages_into_language_and_path(Path::new("posts/alfa.md"), &languages);
+/// //=> ("en", "posts/alfa.md")
+/// ```
+///
+pub fn from_path_and_languages_into_language_and_path<'a>(path: &Path, languages: &'a [String]) -> (&'a str, PathBuf) {
+    let Some(default) = languages.first() else { return ("", path.to_path_buf()) };
+    let stem = path.file_stem().map(|x| x.to_string_lossy().into_owned()).unwrap_or_default();
+    let language = stem.rsplit_once('.').and_then(|(stem, code)| {
+        languages.iter().find(|x| *x == code).map(|x| (stem, x.as_str()))
+    });
+    match language {
+        Some((stem, code)) => {
+            let mut file_name = String::from(stem);
+            if let Some(extension) = path.extension() {
+                file_name.push('.');
+                file_name.push_str(&extension.to_string_lossy());
+            }
+            let path = path.with_file_name(file_name);
+            match code == default {
+                true => (code, path),
+                false => (code, Path::new(code).join(path)),
+            }
+        },
+        None => (default.as_str(), path.to_path_buf()),
+    }
+}
+
+/// Is the language the default language, i.e. the first site language,
+/// which is at the output root? Without any site languages, it is.
+pub fn is_default_language(language: &str, languages: &[String]) -> bool {
+    languages.first().map(|x| x == language).unwrap_or(true)
+}
+
+/// Convert from a language-rooted path into the path without any language
+/// directory, such as "fr/posts/alfa.md" into "posts/alfa.md", which is
+/// the same for each language variant.
+pub fn from_language_and_path_into_translation_key(language: &str, path: &Path, languages: &[String]) -> PathBuf {
+    match is_default_language(language, languages) {
+        true => path.to_path_buf(),
+        false => path.strip_prefix(language).unwrap_or(path).to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path_and_languages_into_language_and_path() {
+        let languages = [String::from("en"), String::from("fr")];
+        let f = |path: &str| {
+            let (language, path) = from_path_and_languages_into_language_and_path(Path::new(path), &languages);
+            (String::from(language), path)
+        };
+        assert_eq!(f("posts/alfa.fr.md"), (String::from("fr"), PathBuf::from("fr/posts/alfa.md")));
+        assert_eq!(f("posts/_index.fr.md"), (String::from("fr"), PathBuf::from("fr/posts/_index.md")));
+        assert_eq!(f("posts/alfa.en.md"), (String::from("en"), PathBuf::from("posts/alfa.md")));
+        assert_eq!(f("posts/alfa.md"), (String::from("en"), PathBuf::from("posts/alfa.md")));
+        assert_eq!(f("posts/alfa.de.md"), (String::from("en"), PathBuf::from("posts/alfa.de.md")));
+        assert_eq!(from_path_and_languages_into_language_and_path(Path::new("alfa.fr.md"), &[]), ("", PathBuf::from("alfa.fr.md")));
+    }
+
+    #[test]
+    fn test_is_default_language() {
+        let languages = [String::from("en"), String::from("fr")];
+        assert!(is_default_language("en", &languages));
+        assert!(!is_default_language("fr", &languages));
+        assert!(is_default_language("", &[]));
+    }
+
+    #[test]
+    fn test_from_language_and_path_into_translation_key() {
+        let languages = [String::from("en"), String::from("fr")];
+        assert_eq!(from_language_and_path_into_translation_key("fr", Path::new("fr/posts/alfa.md"), &languages), PathBuf::from("posts/alfa.md"));
+        assert_eq!(from_language_and_path_into_translation_key("en", Path::new("fr/alfa.md"), &languages), PathBuf::from("fr/alfa.md"));
+    }
+
+}
@@ -0,0 +1,927 @@
+//! Site model, i.e. every page of a build, for templates to list and navigate.
+//!
+//! Each page's state gets the variable `site` with:
+//!
+//! * `site.pages`: every page, in walk order, each with its variables
+//!   such as `title`, `url`, and `description`, but without its content.
+//!
+//! * `site.sections`: every content directory that has pages, in walk order,
+//!   each with `path` such as "posts", `url` such as "/posts/", `title`,
+//!   `pages`, and `subsections`.
+//!
+//! A directory with a section index page `_index.md` is a section.
+//! The index page is rendered to the directory's `index.html`,
+//! with the variable `section` that is its entry in `site.sections`.
+//! Its front matter sets the section variables, such as `title`,
+//! and `sort_by` to sort the section pages by "date", "weight", or "title".
+//!
+//! * `site.taxonomies`: every taxonomy, such as tags, each with `name`,
+//!   `title`, `url`, and `terms`; see `site_taxonomy.rs`.
+//!
+//! * `site.archive`: the archive, if any, with `title`, `url`, and `years`,
+//!   each with its `months`; see `site_archive.rs`.
+//!
+//! Example template for a "recent posts" widget:
+//!
+//! ```handlebars
+//! {{#each site.pages}}<a href="{{url}}">{{title}}</a>{{/each}}
+//! ```
+
+use std::path::{Path, PathBuf};
+use crate::types::{list::*, map::*};
+use crate::state::state_value::StateValue;
+use crate::site::site_archive::*;
+use crate::site::site_feed::*;
+use crate::site::site_language::*;
+use crate::site::site_page::{SitePage, NOT_FOUND_FILE_STEM};
+use crate::site::site_paginator::*;
+use crate::site::site_redirect::*;
+use crate::site::site_search::*;
+use crate::site::site_sitemap::*;
+use crate::site::site_taxonomy::*;
+use crate::state::state_trait::StateTrait;
+use crate::state::state_with_json::StateWithJSON;
+use crate::f::from_date_str_into_system_time::*;
+use crate::f::from_relative_path_into_url_string::*;
+use crate::f::from_str_into_title_string::*;
+
+/// The default sort order of section pages, i.e. newest date first.
+pub const SORT_BY_DEFAULT: &str = "date";
+
+/// The default site feed title, when there's no root section index page title.
+pub const FEED_TITLE_DEFAULT: &str = "Feed";
+
+/// The default number of related pages per page.
+pub const RELATED_COUNT_DEFAULT: usize = 5;
+
+/// The default breadcrumbs root title, when there's no root section index page title.
+pub const BREADCRUMBS_ROOT_TITLE_DEFAULT: &str = "Home";
+
+/// The default not found page title, when there's no "404.md".
+pub const NOT_FOUND_TITLE_DEFAULT: &str = "Page not found";
+
+#[derive(Debug, Default)]
+pub struct SiteModel {
+
+    /// The pages from the input, in walk order.
+    pub pages: List<SitePage>,
+
+    /// The pages generated from the site, such as taxonomy pages,
+    /// which are rendered, yet aren't listed in `site.pages`.
+    pub listings: List<SitePage>,
+
+    /// The taxonomies, such as tags and categories.
+    pub taxonomies: List<SiteTaxonomy>,
+
+    /// The archive of dated pages by year and month, if any.
+    pub archive: Option<SiteArchive>,
+
+}
+
+impl SiteModel {
+
+    /// Convert to a state value for the `site` variable.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let value = site_model.to_state_value();
+    /// //=> {"pages": […], "sections": [{"path": "posts", "url": "/posts/", "pages": […], …}]}
+    /// ```
+    ///
+    pub fn to_state_value(&self) -> StateValue {
+        let pages: List<StateValue> = self.pages.iter()
+            .filter(|x| x.is_listed())
+            .map(SitePage::to_state_value)
+            .collect();
+        let sections: List<StateValue> = self.section_dirs().iter()
+            .map(|dir| self.section_state_value(dir))
+            .collect();
+        let taxonomies: List<StateValue> = self.taxonomies.iter()
+            .map(SiteTaxonomy::to_state_value)
+            .collect();
+        let mut value = StateValue::Map(map!(
+            String::from("pages") => StateValue::List(pages),
+            String::from("sections") => StateValue::List(sections),
+            String::from("taxonomies") => StateValue::List(taxonomies)
+        ));
+        if let Some(archive) = &self.archive {
+            value.deep_merge(StateValue::Map(map!(
+                String::from("archive") => archive.to_state_value()
+            )));
+        }
+        value
+    }
+
+    /// Insert taxonomies by name, such as "tags", from each page's front matter.
+    ///
+    /// This sets each page's variable `taxonomies` to its term links,
+    /// and generates a listing page for each taxonomy and for each term,
+    /// in the output directory, rendered via the template names, if any.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// site_model.insert_taxonomies(&[String::from("tags")], &output, Path::new("html"), None, None);
+    /// //=> site_model.listings has "tags/index.html", "tags/rust/index.html", …
+    /// ```
+    ///
+    pub fn insert_taxonomies(
+        &mut self,
+        names: &[String],
+        output: &Path,
+        extension: &Path,
+        taxonomy_template_name: Option<&str>,
+        term_template_name: Option<&str>,
+    ) {
+        trace!("SiteModel::insert_taxonomies ➡ names: {:?}", names);
+        let taxonomies: List<SiteTaxonomy> = names.iter().map(|name| {
+            let values: List<Option<StateValue>> = self.pages.iter()
+                .map(|x| if x.is_listed() { x.state.get_value(name) } else { None })
+                .collect();
+            from_values_into_site_taxonomy(name, &values)
+        }).collect();
+        for (index, page) in self.pages.iter_mut().enumerate() {
+            let links: Map<String, StateValue> = taxonomies.iter().map(|taxonomy|
+                (
+                    taxonomy.name.to_owned(),
+                    StateValue::List(taxonomy.terms.iter()
+                        .filter(|x| x.page_indexes.contains(&index))
+                        .map(|x| taxonomy.term_link_state_value(x))
+                        .collect())
+                )
+            ).collect();
+            page.state.insert_value(String::from("taxonomies"), StateValue::Map(links));
+        }
+        for taxonomy in taxonomies.iter() {
+            let taxonomy_value = taxonomy.to_state_value();
+            let mut state = StateWithJSON::new();
+            state.insert_value(String::from("title"), StateValue::from(from_str_into_title_string(&taxonomy.name)));
+            state.insert_value(String::from("url"), StateValue::from(taxonomy.url()));
+            state.insert_value(String::from("content"), StateValue::from(String::new()));
+            state.insert_value(String::from("taxonomy"), taxonomy_value.clone());
+            let items: List<StateValue> = taxonomy.terms.iter().map(|x| taxonomy.term_link_state_value(x)).collect();
+            self.listings.push(listing_page(output, taxonomy.output_path(extension), state, items, taxonomy_template_name));
+            for term in taxonomy.terms.iter() {
+                let mut pages: List<&SitePage> = term.page_indexes.iter().map(|&i| &self.pages[i]).collect();
+                sort_pages(&mut pages, SORT_BY_DEFAULT);
+                let items: List<StateValue> = pages.into_iter().map(SitePage::to_state_value).collect();
+                let mut term_value = taxonomy.term_link_state_value(term);
+                term_value.deep_merge(StateValue::Map(map!(
+                    String::from("pages") => StateValue::List(items.clone())
+                )));
+                let mut state = StateWithJSON::new();
+                state.insert_value(String::from("title"), StateValue::from(term.name.to_owned()));
+                state.insert_value(String::from("url"), StateValue::from(taxonomy.term_url(term)));
+                state.insert_value(String::from("content"), StateValue::from(String::new()));
+                state.insert_value(String::from("taxonomy"), taxonomy_value.clone());
+                state.insert_value(String::from("term"), term_value);
+                self.listings.push(listing_page(output, taxonomy.term_output_path(term, extension), state, items, term_template_name));
+            }
+        }
+        self.taxonomies = taxonomies;
+    }
+
+    /// Insert each listed page's variable `related`, i.e. up to `count` other
+    /// pages that share taxonomy terms with it, such as tags.
+    ///
+    /// Pages score by how many terms they share, and ties break by
+    /// title similarity, then by walk order. A page without shared terms
+    /// isn't related, so this needs taxonomies; see `insert_taxonomies`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// site_model.insert_related(5);
+    /// //=> each page has `related`, such as [{"title": "Bravo", "url": "/bravo.html", …}]
+    /// ```
+    ///
+    pub fn insert_related(&mut self, count: usize) {
+        trace!("SiteModel::insert_related ➡ count: {:?}", count);
+        let mut scores: List<Map<usize, usize>> = vec![Map::new(); self.pages.len()];
+        for taxonomy in self.taxonomies.iter() {
+            for term in taxonomy.terms.iter() {
+                for &a in term.page_indexes.iter() {
+                    for &b in term.page_indexes.iter().filter(|&&b| b != a) {
+                        *scores[a].entry(b).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        let titles: List<String> = self.pages.iter()
+            .map(|x| x.state.get_value("title").map(|x| x.to_plain_string().to_lowercase()).unwrap_or_default())
+            .collect();
+        let related: List<Option<StateValue>> = scores.iter().enumerate().map(|(a, scores)| {
+            if !self.pages[a].is_listed() { return None }
+            let mut others: List<(usize, usize, f64)> = scores.iter()
+                .map(|(&b, &score)| (b, score, strsim::normalized_levenshtein(&titles[a], &titles[b])))
+                .collect();
+            others.sort_by(|x, y| y.1.cmp(&x.1).then(y.2.total_cmp(&x.2)).then(x.0.cmp(&y.0)));
+            Some(StateValue::List(others.into_iter().take(count).map(|(b, _, _)| self.pages[b].to_state_value()).collect()))
+        }).collect();
+        for (page, related) in self.pages.iter_mut().zip(related) {
+            if let Some(x) = related {
+                page.state.insert_value(String::from("related"), x);
+            }
+        }
+    }
+
+    /// Insert the archive of dated pages, i.e. an archive index page,
+    /// a page per year, and a page per month, each rendered via its
+    /// template name, if any; see `site_archive.rs`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// site_model.insert_archive(&output, Path::new("html"), None, None, None);
+    /// //=> site_model.listings has "archive/index.html", "archive/2026/index.html", "archive/2026/10/index.html", …
+    /// ```
+    ///
+    pub fn insert_archive(
+        &mut self,
+        output: &Path,
+        extension: &Path,
+        archive_template_name: Option<&str>,
+        year_template_name: Option<&str>,
+        month_template_name: Option<&str>,
+    ) {
+        trace!("SiteModel::insert_archive");
+        let values: List<Option<StateValue>> = self.pages.iter()
+            .map(|x| if x.is_listed() { x.state.get_value("date") } else { None })
+            .collect();
+        let archive = from_values_into_site_archive(&values);
+        let archive_value = archive.to_state_value();
+        let mut state = StateWithJSON::new();
+        state.insert_value(String::from("title"), StateValue::from(ARCHIVE_TITLE_DEFAULT));
+        state.insert_value(String::from("url"), StateValue::from(archive.url()));
+        state.insert_value(String::from("content"), StateValue::from(String::new()));
+        state.insert_value(String::from("archive"), archive_value.clone());
+        let items: List<StateValue> = archive.years.iter().map(|x| archive.year_link_state_value(x)).collect();
+        self.listings.push(listing_page(output, archive.output_path(extension), state, items, archive_template_name));
+        let pages_state_value = |page_indexes: &[usize]| -> List<StateValue> {
+            let mut pages: List<&SitePage> = page_indexes.iter().map(|&i| &self.pages[i]).collect();
+            sort_pages(&mut pages, SORT_BY_DEFAULT);
+            pages.into_iter().map(SitePage::to_state_value).collect()
+        };
+        let mut listings: List<SitePage> = List::new();
+        for year in archive.years.iter() {
+            let year_link = archive.year_link_state_value(year);
+            let items = pages_state_value(&year.page_indexes);
+            let mut year_value = year_link.clone();
+            year_value.deep_merge(StateValue::Map(map!(
+                String::from("months") => StateValue::List(year.months.iter().map(|x| archive.month_link_state_value(year, x)).collect()),
+                String::from("pages") => StateValue::List(items.clone())
+            )));
+            let mut state = StateWithJSON::new();
+            state.insert_value(String::from("title"), StateValue::from(year.year.as_str()));
+            state.insert_value(String::from("url"), StateValue::from(archive.year_url(year)));
+            state.insert_value(String::from("content"), StateValue::from(String::new()));
+            state.insert_value(String::from("archive"), archive_value.clone());
+            state.insert_value(String::from("year"), year_value);
+            listings.push(listing_page(output, archive.year_output_path(year, extension), state, items, year_template_name));
+            for month in year.months.iter() {
+                let items = pages_state_value(&month.page_indexes);
+                let mut month_value = archive.month_link_state_value(year, month);
+                month_value.deep_merge(StateValue::Map(map!(
+                    String::from("pages") => StateValue::List(items.clone())
+                )));
+                let mut state = StateWithJSON::new();
+                state.insert_value(String::from("title"), StateValue::from(month.title(year)));
+                state.insert_value(String::from("url"), StateValue::from(archive.month_url(year, month)));
+                state.insert_value(String::from("content"), StateValue::from(String::new()));
+                state.insert_value(String::from("archive"), archive_value.clone());
+                state.insert_value(String::from("year"), year_link.clone());
+                state.insert_value(String::from("month"), month_value);
+                listings.push(listing_page(output, archive.month_output_path(year, month, extension), state, items, month_template_name));
+            }
+        }
+        self.listings.extend(listings);
+        self.archive = Some(archive);
+    }
+
+    /// Insert the not found page, i.e. "404.html" at the output root,
+    /// rendered via the template name.
+    ///
+    /// When the input has a not found page, such as "404.md", this sets
+    /// its template name; otherwise this generates a listing page.
+    pub fn insert_not_found_page(&mut self, output: &Path, extension: &Path, template_name: &str) {
+        trace!("SiteModel::insert_not_found_page ➡ template_name: {:?}", template_name);
+        if let Some(page) = self.pages.iter_mut().find(|x| x.is_not_found()) {
+            page.template_name = Some(String::from(template_name));
+            return
+        }
+        let path = PathBuf::from(NOT_FOUND_FILE_STEM).with_extension(extension);
+        let mut state = StateWithJSON::new();
+        state.insert_value(String::from("title"), StateValue::from(NOT_FOUND_TITLE_DEFAULT));
+        state.insert_value(String::from("url"), StateValue::from(from_relative_path_into_url_string(&path)));
+        state.insert_value(String::from("content"), StateValue::from(String::new()));
+        let mut page = listing_page(output, path, state, List::new(), Some(template_name));
+        page.items = None;
+        self.listings.push(page);
+    }
+
+    /// Insert the variables that differ per page, such as `section`
+    /// for a section index page, whose items are the section pages.
+    ///
+    /// Each listed page gets `prev` and `next`, which link to the nearby
+    /// pages in its section order, if any, and each listed page and
+    /// section index page gets `breadcrumbs`.
+    pub fn insert_page_variables(&mut self) {
+        let sections: List<(PathBuf, StateValue)> = self.pages.iter()
+            .filter(|x| x.is_section_index())
+            .map(|x| (x.dir().to_path_buf(), self.section_state_value(x.dir())))
+            .collect();
+        let mut neighbors: Map<PathBuf, (Option<StateValue>, Option<StateValue>)> = Map::new();
+        for dir in self.section_dirs() {
+            let pages = self.section_pages(&dir);
+            for (i, page) in pages.iter().enumerate() {
+                let prev = i.checked_sub(1).map(|j| pages[j].to_link_state_value());
+                let next = pages.get(i + 1).map(|x| x.to_link_state_value());
+                neighbors.insert(page.path.to_owned(), (prev, next));
+            }
+        }
+        let breadcrumbs: Map<PathBuf, StateValue> = self.pages.iter()
+            .filter(|x| x.is_listed() || x.is_section_index())
+            .map(|x| (x.path.to_owned(), self.breadcrumbs_state_value(x)))
+            .collect();
+        for page in self.pages.iter_mut() {
+            if let Some((prev, next)) = neighbors.remove(&page.path) {
+                if let Some(x) = prev { page.state.insert_value(String::from("prev"), x); }
+                if let Some(x) = next { page.state.insert_value(String::from("next"), x); }
+            }
+            if let Some(x) = breadcrumbs.get(&page.path) {
+                page.state.insert_value(String::from("breadcrumbs"), x.to_owned());
+            }
+        }
+        for (dir, section) in sections {
+            if let Some(page) = self.pages.iter_mut().find(|x| x.is_section_index() && x.dir() == dir) {
+                if let StateValue::Map(x) = &section {
+                    if let Some(StateValue::List(items)) = x.get("pages") {
+                        page.items = Some(items.to_owned());
+                    }
+                }
+                page.state.insert_value(String::from("section"), section);
+            }
+        }
+    }
+
+    /// Paginate each page and listing that has items, which adds a listing
+    /// for each next chunk; see `site_paginator.rs`.
+    pub fn paginate(&mut self, paginate_by: Option<usize>) {
+        let mut next_pages: List<SitePage> = List::new();
+        for page in self.pages.iter_mut().chain(self.listings.iter_mut()) {
+            next_pages.extend(paginate_site_page(page, paginate_by));
+        }
+        self.listings.extend(next_pages);
+    }
+
+    /// Get the feeds: one for the whole site, and one per section that has
+    /// a section index page, each with up to `length` entries.
+    ///
+    /// The site feed title is the root section index page title, if any.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let feeds = site_model.feeds(20);
+    /// //=> [SiteFeed { title: "Feed", path: "", … }, SiteFeed { title: "Blog", path: "posts", … }]
+    /// ```
+    ///
+    pub fn feeds(&self, length: usize) -> List<SiteFeed> {
+        let title = |dir: &Path, default: &str| self.section_index(dir)
+            .and_then(|x| x.state.get_value("title"))
+            .map(|x| x.to_plain_string())
+            .filter(|x| !x.is_empty())
+            .unwrap_or_else(|| String::from(default));
+        let pages: List<&SitePage> = self.pages.iter().filter(|x| x.is_listed()).collect();
+        let mut feeds = vec![SiteFeed::from_pages(&title(Path::new(""), FEED_TITLE_DEFAULT), Path::new(""), &pages, length)];
+        for dir in self.section_dirs() {
+            if dir.as_os_str().is_empty() || self.section_index(&dir).is_none() { continue; }
+            let pages: List<&SitePage> = pages.iter().filter(|x| x.dir() == dir).copied().collect();
+            let default = from_str_into_title_string(&dir.file_name().unwrap_or_default().to_string_lossy());
+            feeds.push(SiteFeed::from_pages(&title(&dir, &default), &dir, &pages, length));
+        }
+        feeds
+    }
+
+    /// Get the search index of every listed page, with each page's fields,
+    /// or only the pages in the sections, such as "docs", and their subsections.
+    pub fn search(&self, sections: Option<&[String]>, fields: &[String]) -> SiteSearch {
+        let pages: List<&SitePage> = self.pages.iter()
+            .filter(|x| x.is_listed())
+            .filter(|x| sections.is_none_or(|sections| sections.iter().any(|section| x.dir().starts_with(section))))
+            .collect();
+        SiteSearch::from_pages(&pages, fields)
+    }
+
+    /// Get the sitemap of every page and listing, in render order,
+    /// except the not found page.
+    ///
+    /// Each `lastmod` is the front matter `updated` or `date`, if any,
+    /// or else the input file modified time, if any.
+    pub fn sitemap(&self) -> SiteSitemap {
+        let urls: List<SiteSitemapUrl> = self.pages.iter().chain(self.listings.iter()).filter(|x| !x.is_not_found()).map(|page| {
+            let lastmod = ["updated", "date"].iter()
+                .find_map(|key| page.state.get_value(key).and_then(|x| from_date_str_into_system_time(&x.to_plain_string())))
+                .or_else(|| std::fs::metadata(&page.input).and_then(|x| x.modified()).ok());
+            SiteSitemapUrl {
+                url: page.state.get_value("url").map(|x| x.to_plain_string()).unwrap_or_default(),
+                lastmod,
+            }
+        }).collect();
+        SiteSitemap { urls }
+    }
+
+    /// Insert each page's variable `translations`, i.e. a map of each
+    /// language variant's code to its URL, including the page itself.
+    ///
+    /// The variants of a page share the same path without any language
+    /// directory, such as "about.md" and "fr/about.md"; see `site_language.rs`.
+    pub fn insert_translations(&mut self, languages: &[String]) {
+        let key = |page: &SitePage| -> PathBuf {
+            let language = page.state.get_value("lang").map(|x| x.to_plain_string()).unwrap_or_default();
+            from_language_and_path_into_translation_key(&language, &page.path, languages)
+        };
+        let mut translations_by_key: Map<PathBuf, Map<String, StateValue>> = Map::new();
+        for page in self.pages.iter() {
+            if let (Some(language), Some(url)) = (page.state.get_value("lang"), page.state.get_value("url")) {
+                translations_by_key.entry(key(page)).or_default().insert(language.to_plain_string(), url);
+            }
+        }
+        for page in self.pages.iter_mut() {
+            if let Some(translations) = translations_by_key.get(&key(page)) {
+                page.state.insert_value(String::from("translations"), StateValue::Map(translations.to_owned()));
+            }
+        }
+    }
+
+    /// Get the permalinks, i.e. a map of each page's root-relative input path,
+    /// such as "/posts/alfa.md", to its URL, such as "/blog/2026/alfa/",
+    /// for rewriting Markdown links.
+    ///
+    /// A page is only in the map when its URL differs from its input path
+    /// with the output file name extension, such as "html", such as via
+    /// a permalink pattern, or front matter `url`, or for a section index page.
+    pub fn permalinks(&self, extension: &Path) -> Map<String, String> {
+        self.pages.iter().filter_map(|page| {
+            let url = page.state.get_value("url").map(|x| x.to_plain_string())?;
+            let html = from_relative_path_into_url_string(&page.path.with_extension(extension));
+            (html != url).then(|| (from_relative_path_into_url_string(&page.path), url))
+        }).collect()
+    }
+
+    /// Get the redirects, i.e. each page's front matter `aliases`, in walk order,
+    /// then each entry of the redirect map, from an old URL to a new URL.
+    ///
+    /// Example front matter:
+    ///
+    /// ```yaml
+    /// aliases: [/old/path/, /older/path.html]
+    /// ```
+    ///
+    pub fn redirects(&self, redirect_map: Option<&Map<String, String>>) -> List<SiteRedirect> {
+        let mut redirects: List<SiteRedirect> = List::new();
+        for page in self.pages.iter() {
+            let url = page.state.get_value("url").map(|x| x.to_plain_string()).unwrap_or_default();
+            let aliases = match page.state.get_value("aliases") {
+                Some(StateValue::List(x)) => x,
+                Some(StateValue::String(x)) => vec![StateValue::String(x)],
+                _ => continue,
+            };
+            for alias in aliases.iter() {
+                redirects.push(SiteRedirect::new(&alias.to_plain_string(), &url));
+            }
+        }
+        if let Some(redirect_map) = redirect_map {
+            for (from, to) in redirect_map.iter() {
+                redirects.push(SiteRedirect::new(from, to));
+            }
+        }
+        redirects
+    }
+
+    /// Get the section directories, in walk order, i.e. each directory
+    /// that has a page or a section index page.
+    fn section_dirs(&self) -> List<PathBuf> {
+        let mut dirs: List<PathBuf> = List::new();
+        for page in self.pages.iter().filter(|x| !x.is_not_found()) {
+            if !dirs.iter().any(|x| x == page.dir()) {
+                dirs.push(page.dir().to_path_buf());
+            }
+        }
+        dirs
+    }
+
+    /// Get the section index page of a directory, if any.
+    fn section_index(&self, dir: &Path) -> Option<&SitePage> {
+        self.pages.iter().find(|x| x.is_section_index() && x.dir() == dir)
+    }
+
+    /// Get the section sort order, from its index page `sort_by`, if any.
+    fn section_sort_by(&self, dir: &Path) -> String {
+        self.section_index(dir)
+            .and_then(|x| x.state.get_value("sort_by"))
+            .map(|x| x.to_plain_string())
+            .unwrap_or_else(|| String::from(SORT_BY_DEFAULT))
+    }
+
+    /// Get the section pages, i.e. the listed pages of the directory, sorted.
+    fn section_pages(&self, dir: &Path) -> List<&SitePage> {
+        let mut pages: List<&SitePage> = self.pages.iter()
+            .filter(|x| x.is_listed() && x.dir() == dir)
+            .collect();
+        sort_pages(&mut pages, &self.section_sort_by(dir));
+        pages
+    }
+
+    /// Convert a page to its breadcrumbs, i.e. a link to each ancestor
+    /// directory, from the site root, with the directory's index title.
+    ///
+    /// A section index page's own directory isn't its ancestor,
+    /// so "posts/_index.md" gets only the root link.
+    fn breadcrumbs_state_value(&self, page: &SitePage) -> StateValue {
+        let dir = match page.is_section_index() {
+            true => page.dir().parent(),
+            false => Some(page.dir()),
+        };
+        let mut dirs: List<&Path> = dir.map(|x| x.ancestors().collect()).unwrap_or_default();
+        dirs.reverse();
+        StateValue::List(dirs.into_iter().map(|dir| {
+            let title = self.section_index(dir)
+                .and_then(|x| x.state.get_value("title"))
+                .map(|x| x.to_plain_string())
+                .filter(|x| !x.is_empty())
+                .unwrap_or_else(|| match dir.file_name() {
+                    Some(x) => from_str_into_title_string(&x.to_string_lossy()),
+                    None => String::from(BREADCRUMBS_ROOT_TITLE_DEFAULT),
+                });
+            StateValue::Map(map!(
+                String::from("title") => StateValue::from(title),
+                String::from("url") => StateValue::from(from_relative_path_into_url_string(&dir.join(INDEX_FILE_NAME_AS_STR)))
+            ))
+        }).collect())
+    }
+
+    /// Convert a section directory to a state value for `site.sections`,
+    /// with the section pages sorted, and with the direct subsections.
+    fn section_state_value(&self, dir: &Path) -> StateValue {
+        let sort_by = self.section_sort_by(dir);
+        let pages = self.section_pages(dir);
+        let subsections: List<StateValue> = self.section_dirs().iter()
+            .filter(|x| x.parent() == Some(dir))
+            .map(|x| self.section_summary_state_value(x))
+            .collect();
+        let mut value = self.section_summary_state_value(dir);
+        value.deep_merge(StateValue::Map(map!(
+            String::from("sort_by") => StateValue::String(sort_by),
+            String::from("pages") => StateValue::List(pages.into_iter().map(SitePage::to_state_value).collect()),
+            String::from("subsections") => StateValue::List(subsections)
+        )));
+        value
+    }
+
+    /// Convert a section directory to a state value without its pages,
+    /// i.e. the index page variables, if any, plus `path`, `url`, and `title`.
+    fn section_summary_state_value(&self, dir: &Path) -> StateValue {
+        let mut value = match self.section_index(dir) {
+            Some(index) => index.to_state_value(),
+            None => StateValue::Map(map!(
+                String::from("title") => StateValue::String(dir.file_name().map(|x| from_str_into_title_string(&x.to_string_lossy())).unwrap_or_default())
+            )),
+        };
+        value.deep_merge(StateValue::Map(map!(
+            String::from("path") => StateValue::from(dir.to_string_lossy().as_ref()),
+            String::from("url") => StateValue::from(from_relative_path_into_url_string(&dir.join(INDEX_FILE_NAME_AS_STR)))
+        )));
+        value
+    }
+
+}
+
+/// Create a listing page, i.e. a page generated from the site, without an input.
+fn listing_page(output: &Path, path: PathBuf, state: StateWithJSON, items: List<StateValue>, template_name: Option<&str>) -> SitePage {
+    SitePage {
+        input: PathBuf::new(),
+        output: output.join(&path),
+        path,
+        html: String::new(),
+        text: String::new(),
+        headings: List::new(),
+        template_name: template_name.map(String::from),
+        items: Some(items),
+        state: Box::new(state),
+    }
+}
+
+/// Sort pages by "date" newest first, or by "weight" lowest first, or by "title".
+///
+/// Pages that lack the sort key go last, and ties keep their walk order.
+pub fn sort_pages(pages: &mut [&SitePage], sort_by: &str) {
+    match sort_by {
+        "weight" => pages.sort_by(|a, b| {
+            let a = a.state.get_value("weight").and_then(|x| state_value_as_f64(&x));
+            let b = b.state.get_value("weight").and_then(|x| state_value_as_f64(&x));
+            match (a, b) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (a, b) => a.is_none().cmp(&b.is_none()),
+            }
+        }),
+        "title" => pages.sort_by(|a, b| {
+            let a = a.state.get_value("title").map(|x| x.to_plain_string().to_lowercase());
+            let b = b.state.get_value("title").map(|x| x.to_plain_string().to_lowercase());
+            match (a, b) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (a, b) => a.is_none().cmp(&b.is_none()),
+            }
+        }),
+        _ => pages.sort_by(|a, b| {
+            let a = a.state.get_value("date").map(|x| x.to_plain_string());
+            let b = b.state.get_value("date").map(|x| x.to_plain_string());
+            match (a, b) {
+                (Some(a), Some(b)) => b.cmp(&a),
+                (a, b) => a.is_none().cmp(&b.is_none()),
+            }
+        }),
+    }
+}
+
+fn state_value_as_f64(x: &StateValue) -> Option<f64> {
+    match x {
+        StateValue::Int(x) => Some(*x as f64),
+        StateValue::Float(x) => Some(*x),
+        StateValue::String(x) => x.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_state_value() {
+        let site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"title": "Alfa"})),
+            SitePage::from_json("posts/bravo.md", json!({"title": "Bravo"})),
+            SitePage::from_json("posts/charlie.md", json!({"title": "Charlie"})),
+        ], ..Default::default() };
+        assert_eq!(site_model.to_state_value(), StateValue::from(json!({
+            "pages": [{"title": "Alfa"}, {"title": "Bravo"}, {"title": "Charlie"}],
+            "sections": [
+                {"path": "", "url": "/", "title": "", "sort_by": "date", "pages": [{"title": "Alfa"}], "subsections": [
+                    {"path": "posts", "url": "/posts/", "title": "Posts"},
+                ]},
+                {"path": "posts", "url": "/posts/", "title": "Posts", "sort_by": "date", "pages": [{"title": "Bravo"}, {"title": "Charlie"}], "subsections": []},
+            ],
+            "taxonomies": [],
+        })));
+    }
+
+    #[test]
+    fn test_insert_taxonomies() {
+        let mut site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"title": "Alfa", "date": "2026-01-01", "tags": ["Rust", "web"]})),
+            SitePage::from_json("bravo.md", json!({"title": "Bravo"})),
+            SitePage::from_json("charlie.md", json!({"title": "Charlie", "date": "2026-02-01", "tags": "rust"})),
+        ], ..Default::default() };
+        site_model.insert_taxonomies(&[String::from("tags")], Path::new("out"), Path::new("html"), Some("taxonomy.html"), None);
+        assert_eq!(site_model.pages[0].state.get_value("taxonomies"), Some(StateValue::from(json!({
+            "tags": [
+                {"name": "Rust", "slug": "rust", "url": "/tags/rust/"},
+                {"name": "web", "slug": "web", "url": "/tags/web/"},
+            ],
+        }))));
+        assert_eq!(site_model.pages[1].state.get_value("taxonomies"), Some(StateValue::from(json!({"tags": []}))));
+        let outputs: List<PathBuf> = site_model.listings.iter().map(|x| x.output.to_owned()).collect();
+        assert_eq!(outputs, vec![
+            PathBuf::from("out/tags/index.html"),
+            PathBuf::from("out/tags/rust/index.html"),
+            PathBuf::from("out/tags/web/index.html"),
+        ]);
+        assert_eq!(site_model.listings[0].template_name.as_deref(), Some("taxonomy.html"));
+        assert_eq!(site_model.listings[1].template_name, None);
+        let term_pages = site_model.listings[1].state.get_value("term")
+            .and_then(|x| match x { StateValue::Map(mut x) => x.remove("pages"), _ => None });
+        let titles: List<StateValue> = match term_pages {
+            Some(StateValue::List(x)) => x.into_iter().filter_map(|x| match x { StateValue::Map(mut x) => x.remove("title"), _ => None }).collect(),
+            _ => List::new(),
+        };
+        assert_eq!(titles, vec![StateValue::from("Charlie"), StateValue::from("Alfa")]);
+    }
+
+    #[test]
+    fn test_insert_page_variables_x_section() {
+        let mut site_model = SiteModel { pages: vec![
+            SitePage::from_json("posts/_index.md", json!({"title": "Blog", "sort_by": "weight"})),
+            SitePage::from_json("posts/alfa.md", json!({"title": "Alfa", "weight": 2})),
+            SitePage::from_json("posts/bravo.md", json!({"title": "Bravo", "weight": 1})),
+            SitePage::from_json("posts/charlie.md", json!({"title": "Charlie"})),
+            SitePage::from_json("posts/2026/delta.md", json!({"title": "Delta"})),
+        ], ..Default::default() };
+        site_model.insert_page_variables();
+        assert_eq!(site_model.pages[0].state.get_value("section"), Some(StateValue::from(json!({
+            "path": "posts",
+            "url": "/posts/",
+            "title": "Blog",
+            "sort_by": "weight",
+            "pages": [
+                {"title": "Bravo", "weight": 1},
+                {"title": "Alfa", "weight": 2},
+                {"title": "Charlie"},
+            ],
+            "subsections": [
+                {"path": "posts/2026", "url": "/posts/2026/", "title": "2026"},
+            ],
+        }))));
+        assert_eq!(site_model.pages[1].state.get_value("section"), None);
+    }
+
+    #[test]
+    fn test_insert_page_variables_x_prev_next_and_breadcrumbs() {
+        let mut site_model = SiteModel { pages: vec![
+            SitePage::from_json("posts/_index.md", json!({"title": "Blog", "url": "/posts/", "sort_by": "weight"})),
+            SitePage::from_json("posts/alfa.md", json!({"title": "Alfa", "url": "/posts/alfa.html", "weight": 2})),
+            SitePage::from_json("posts/bravo.md", json!({"title": "Bravo", "url": "/posts/bravo.html", "weight": 1})),
+            SitePage::from_json("posts/2026/charlie.md", json!({"title": "Charlie", "url": "/posts/2026/charlie.html"})),
+        ], ..Default::default() };
+        site_model.insert_page_variables();
+        let get = |i: usize, key: &str| site_model.pages[i].state.get_value(key);
+        assert_eq!(get(2, "prev"), None);
+        assert_eq!(get(2, "next"), Some(StateValue::from(json!({"title": "Alfa", "url": "/posts/alfa.html"}))));
+        assert_eq!(get(1, "prev"), Some(StateValue::from(json!({"title": "Bravo", "url": "/posts/bravo.html"}))));
+        assert_eq!(get(1, "next"), None);
+        assert_eq!(get(0, "prev"), None);
+        assert_eq!(get(0, "breadcrumbs"), Some(StateValue::from(json!([
+            {"title": "Home", "url": "/"},
+        ]))));
+        assert_eq!(get(3, "breadcrumbs"), Some(StateValue::from(json!([
+            {"title": "Home", "url": "/"},
+            {"title": "Blog", "url": "/posts/"},
+            {"title": "2026", "url": "/posts/2026/"},
+        ]))));
+    }
+
+    #[test]
+    fn test_feeds() {
+        let site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"title": "Alfa", "date": "2026-01-01"})),
+            SitePage::from_json("posts/_index.md", json!({"title": "Blog"})),
+            SitePage::from_json("posts/bravo.md", json!({"title": "Bravo", "date": "2026-02-01"})),
+            SitePage::from_json("notes/charlie.md", json!({"title": "Charlie", "date": "2026-03-01"})),
+        ], ..Default::default() };
+        let feeds = site_model.feeds(2);
+        assert_eq!(feeds.iter().map(|x| (x.title.as_str(), x.path.to_string_lossy().into_owned())).collect::<List<(&str, String)>>(), vec![
+            ("Feed", String::new()),
+            ("Blog", String::from("posts")),
+        ]);
+        assert_eq!(feeds[0].entries.iter().map(|x| x.title.as_str()).collect::<List<&str>>(), vec!["Charlie", "Bravo"]);
+        assert_eq!(feeds[1].entries.iter().map(|x| x.title.as_str()).collect::<List<&str>>(), vec!["Bravo"]);
+    }
+
+    #[test]
+    fn test_insert_related() {
+        let mut site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"title": "Rust web apps", "tags": ["rust", "web", "apps"]})),
+            SitePage::from_json("bravo.md", json!({"title": "Rust", "tags": ["rust"]})),
+            SitePage::from_json("charlie.md", json!({"title": "Rust web app", "tags": ["rust", "web"]})),
+            SitePage::from_json("delta.md", json!({"title": "Rust web", "tags": ["rust", "web"]})),
+            SitePage::from_json("echo.md", json!({"title": "Echo", "tags": ["go"]})),
+        ], ..Default::default() };
+        site_model.insert_taxonomies(&[String::from("tags")], Path::new("out"), Path::new("html"), None, None);
+        site_model.insert_related(2);
+        let titles = |i: usize| match site_model.pages[i].state.get_value("related") {
+            Some(StateValue::List(x)) => x.iter().map(|x| match x {
+                StateValue::Map(x) => x.get("title").map(|x| x.to_plain_string()).unwrap_or_default(),
+                _ => String::new(),
+            }).collect::<List<String>>(),
+            _ => panic!("related"),
+        };
+        assert_eq!(titles(0), vec!["Rust web app", "Rust web"]);
+        assert_eq!(titles(1), vec!["Rust web", "Rust web app"]);
+        assert!(titles(4).is_empty());
+    }
+
+    #[test]
+    fn test_insert_archive() {
+        let mut site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"title": "Alfa", "date": "2026-01-02"})),
+            SitePage::from_json("bravo.md", json!({"title": "Bravo"})),
+            SitePage::from_json("charlie.md", json!({"title": "Charlie", "date": "2026-01-20"})),
+        ], ..Default::default() };
+        site_model.insert_archive(Path::new("out"), Path::new("html"), Some("archive.html"), None, None);
+        assert_eq!(site_model.listings.iter().map(|x| x.output.to_string_lossy().into_owned()).collect::<List<String>>(), vec![
+            "out/archive/index.html",
+            "out/archive/2026/index.html",
+            "out/archive/2026/01/index.html",
+        ]);
+        assert_eq!(site_model.listings[0].template_name, Some(String::from("archive.html")));
+        assert_eq!(site_model.listings[2].state.get_value("month"), Some(StateValue::from(json!({
+            "year": "2026", "month": "01", "title": "January 2026", "url": "/archive/2026/01/", "count": 2,
+            "pages": [{"title": "Charlie", "date": "2026-01-20"}, {"title": "Alfa", "date": "2026-01-02"}],
+        }))));
+        assert!(matches!(site_model.to_state_value(), StateValue::Map(x) if x.contains_key("archive")));
+    }
+
+    #[test]
+    fn test_search() {
+        let site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"title": "Alfa"})),
+            SitePage::from_json("docs/_index.md", json!({"title": "Docs"})),
+            SitePage::from_json("docs/bravo.md", json!({"title": "Bravo"})),
+            SitePage::from_json("docs/api/charlie.md", json!({"title": "Charlie"})),
+        ], ..Default::default() };
+        let fields = [String::from("title")];
+        let titles = |search: SiteSearch| search.pages.iter().map(|x| x["title"].to_plain_string()).collect::<List<String>>();
+        assert_eq!(titles(site_model.search(None, &fields)), vec!["Alfa", "Bravo", "Charlie"]);
+        assert_eq!(titles(site_model.search(Some(&[String::from("docs")]), &fields)), vec!["Bravo", "Charlie"]);
+    }
+
+    #[test]
+    fn test_insert_translations() {
+        let languages = [String::from("en"), String::from("fr")];
+        let mut site_model = SiteModel { pages: vec![
+            SitePage::from_json("about.md", json!({"url": "/about.html", "lang": "en"})),
+            SitePage::from_json("fr/about.md", json!({"url": "/fr/about.html", "lang": "fr"})),
+            SitePage::from_json("alfa.md", json!({"url": "/alfa.html", "lang": "en"})),
+        ], ..Default::default() };
+        site_model.insert_translations(&languages);
+        let translations = |i: usize| site_model.pages[i].state.get_value("translations");
+        let about = StateValue::from(json!({"en": "/about.html", "fr": "/fr/about.html"}));
+        assert_eq!(translations(0), Some(about.clone()));
+        assert_eq!(translations(1), Some(about));
+        assert_eq!(translations(2), Some(StateValue::from(json!({"en": "/alfa.html"}))));
+    }
+
+    #[test]
+    fn test_permalinks() {
+        let site_model = SiteModel { pages: vec![
+            SitePage::from_json("posts/alfa.md", json!({"url": "/blog/2026/alfa/"})),
+            SitePage::from_json("posts/_index.md", json!({"url": "/posts/"})),
+            SitePage::from_json("posts/bravo.md", json!({"url": "/posts/bravo.html"})),
+        ], ..Default::default() };
+        assert_eq!(site_model.permalinks(Path::new("html")), map!(
+            String::from("/posts/alfa.md") => String::from("/blog/2026/alfa/"),
+            String::from("/posts/_index.md") => String::from("/posts/")
+        ));
+    }
+
+    #[test]
+    fn test_permalinks_x_extension() {
+        let site_model = SiteModel { pages: vec![
+            SitePage::from_json("posts/alfa.md", json!({"url": "/posts/alfa.htm"})),
+            SitePage::from_json("posts/bravo.md", json!({"url": "/posts/bravo.html"})),
+        ], ..Default::default() };
+        assert_eq!(site_model.permalinks(Path::new("htm")), map!(
+            String::from("/posts/bravo.md") => String::from("/posts/bravo.html")
+        ));
+    }
+
+    #[test]
+    fn test_redirects() {
+        let site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"url": "/alfa.html", "aliases": ["/old/alfa/", "old-alfa.html"]})),
+            SitePage::from_json("bravo.md", json!({"url": "/bravo.html", "aliases": "/old/bravo/"})),
+            SitePage::from_json("charlie.md", json!({"url": "/charlie.html"})),
+        ], ..Default::default() };
+        let redirect_map = map!(String::from("/blog/") => String::from("/posts/"));
+        assert_eq!(site_model.redirects(Some(&redirect_map)), vec![
+            SiteRedirect::new("/old/alfa/", "/alfa.html"),
+            SiteRedirect::new("/old-alfa.html", "/alfa.html"),
+            SiteRedirect::new("/old/bravo/", "/bravo.html"),
+            SiteRedirect::new("/blog/", "/posts/"),
+        ]);
+    }
+
+    #[test]
+    fn test_sitemap() {
+        let site_model = SiteModel { pages: vec![
+            SitePage::from_json("alfa.md", json!({"url": "/alfa.html", "date": "2026-01-02", "updated": "2026-03-04"})),
+            SitePage::from_json("bravo.md", json!({"url": "/bravo.html", "date": "2026-01-02"})),
+        ], listings: vec![
+            SitePage::from_json("tags/index.html", json!({"url": "/tags/"})),
+        ], ..Default::default() };
+        assert_eq!(site_model.sitemap().urls, vec![
+            SiteSitemapUrl { url: String::from("/alfa.html"), lastmod: from_date_str_into_system_time("2026-03-04") },
+            SiteSitemapUrl { url: String::from("/bravo.html"), lastmod: from_date_str_into_system_time("2026-01-02") },
+            SiteSitemapUrl { url: String::from("/tags/"), lastmod: None },
+        ]);
+    }
+
+    #[test]
+    fn test_sort_pages() {
+        let pages = [
+            SitePage::from_json("alfa.md", json!({"title": "alfa", "date": "2026-01-01"})),
+            SitePage::from_json("bravo.md", json!({"title": "Bravo"})),
+            SitePage::from_json("charlie.md", json!({"title": "charlie", "date": "2026-03-01"})),
+        ];
+        let paths = |sort_by: &str| {
+            let mut x: List<&SitePage> = pages.iter().collect();
+            sort_pages(&mut x, sort_by);
+            x.iter().map(|x| x.path.to_string_lossy().into_owned()).collect::<List<String>>()
+        };
+        assert_eq!(paths("date"), vec!["charlie.md", "alfa.md", "bravo.md"]);
+        assert_eq!(paths("title"), vec!["alfa.md", "bravo.md", "charlie.md"]);
+    }
+
+}
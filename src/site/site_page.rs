@@ -0,0 +1,244 @@
+//! Site page, i.e. one page that is parsed and ready to render.
+//!
+//! The first phase of a build parses each input into a site page,
+//! with its full state. The second phase renders each site page,
+//! with the whole site available to its template.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::state::state_trait::StateTrait;
+use crate::state::state_value::StateValue;
+use crate::types::{list::*, map::*};
+use crate::f::from_date_str_into_system_time::*;
+
+/// State keys that a page keeps to itself, rather than sharing via `site.pages`.
+///
+/// The content and table of contents can be large, and the site-wide layers
+/// such as `data` are the same for every page, so listing them would only
+/// multiply the size of every page's state.
+pub const SITE_PAGE_PRIVATE_KEYS: [&str; 11] = ["content", "data", "env", "paginator", "related", "section", "site", "taxonomy", "term", "toc", "toc_html"];
+
+/// The file stem of a section index page, such as "posts/_index.md".
+pub const SECTION_INDEX_FILE_STEM: &str = "_index";
+
+/// The file stem of the not found page, i.e. "404.md" at the input root.
+pub const NOT_FOUND_FILE_STEM: &str = "404";
+
+#[derive(Debug)]
+pub struct SitePage {
+
+    /// The input file path, such as "posts/alfa.md".
+    pub input: PathBuf,
+
+    /// The output file path, such as "posts/alfa.html".
+    pub output: PathBuf,
+
+    /// The input file path relative to the input directory, such as "posts/alfa.md".
+    pub path: PathBuf,
+
+    /// The content as HTML, before any templating.
+    pub html: String,
+
+    /// The content as plain text, such as for a search index.
+    pub text: String,
+
+    /// The content heading texts, in document order, such as for a search index.
+    pub headings: List<String>,
+
+    /// The template name to render with, if any, rather than the default.
+    pub template_name: Option<String>,
+
+    /// The items to paginate, if any, such as a section index page's pages.
+    pub items: Option<List<StateValue>>,
+
+    /// The page state, with front matter, defaults, and computed variables.
+    pub state: Box<dyn StateTrait>,
+
+}
+
+impl SitePage {
+
+    /// Get the directory path relative to the input directory, such as "posts".
+    pub fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+
+    /// Is this page a section index page, such as "posts/_index.md"?
+    pub fn is_section_index(&self) -> bool {
+        is_section_index_path(&self.path)
+    }
+
+    /// Is this page the not found page, such as "404.md" at the input root?
+    pub fn is_not_found(&self) -> bool {
+        is_not_found_path(&self.path)
+    }
+
+    /// Is this page listed, such as in `site.pages`, sections, and feeds?
+    /// A section index page and the not found page aren't listed.
+    pub fn is_listed(&self) -> bool {
+        !self.is_section_index() && !self.is_not_found()
+    }
+
+    /// Is this page a draft, i.e. its front matter `draft` is true?
+    pub fn is_draft(&self) -> bool {
+        match self.state.get_value("draft") {
+            Some(StateValue::Bool(x)) => x,
+            Some(StateValue::String(x)) => x == "true",
+            _ => false,
+        }
+    }
+
+    /// Get the publish time, from the front matter `publish_date`, or else `date`, if any.
+    pub fn publish_time(&self) -> Option<SystemTime> {
+        self.date_time("publish_date").or_else(|| self.date_time("date"))
+    }
+
+    /// Get the expiry time, from the front matter `expiry_date`, if any.
+    pub fn expiry_time(&self) -> Option<SystemTime> {
+        self.date_time("expiry_date")
+    }
+
+    /// Is this page scheduled, i.e. its publish time is after now?
+    pub fn is_future(&self, now: SystemTime) -> bool {
+        self.publish_time().map(|x| x > now).unwrap_or(false)
+    }
+
+    /// Is this page expired, i.e. its expiry time is at or before now?
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.expiry_time().map(|x| x <= now).unwrap_or(false)
+    }
+
+    /// Is this page published, i.e. not expired, and not a draft unless
+    /// drafts are wanted, and not scheduled unless future pages are wanted?
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let published = page.is_published(args.drafts, args.future, SystemTime::now());
+    /// ```
+    ///
+    pub fn is_published(&self, drafts: bool, future: bool, now: SystemTime) -> bool {
+        (drafts || !self.is_draft())
+        && (future || !self.is_future(now))
+        && !self.is_expired(now)
+    }
+
+    /// Get a front matter date as a time, if any, such as `date`.
+    fn date_time(&self, key: &str) -> Option<SystemTime> {
+        self.state.get_value(key).and_then(|x| from_date_str_into_system_time(&x.to_plain_string()))
+    }
+
+    /// Convert to a state value link, with its title and URL, such as for `prev` and `next`.
+    pub fn to_link_state_value(&self) -> StateValue {
+        StateValue::Map(map!(
+            String::from("title") => self.state.get_value("title").unwrap_or(StateValue::Null),
+            String::from("url") => self.state.get_value("url").unwrap_or(StateValue::Null)
+        ))
+    }
+
+    /// Convert to a state value for listing in `site.pages`,
+    /// without the private keys.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let value = page.to_state_value();
+    /// //=> {"title": "Alfa", "url": "/posts/alfa.html", …}
+    /// ```
+    ///
+    pub fn to_state_value(&self) -> StateValue {
+        let map: Map<String, StateValue> = self.state.iter_values()
+            .filter(|(key, _)| !SITE_PAGE_PRIVATE_KEYS.contains(&key.as_str()))
+            .collect();
+        StateValue::Map(map)
+    }
+
+}
+
+/// Is the path a section index page path, such as "posts/_index.md"?
+pub fn is_section_index_path(path: &Path) -> bool {
+    path.file_stem().map(|x| x == SECTION_INDEX_FILE_STEM).unwrap_or(false)
+}
+
+/// Is the path the not found page path, such as "404.md" at the input root?
+///
+/// Hosts serve this page for any missing URL, at any depth, so its links
+/// must be root-relative; see `rewriting/lol.rs`.
+pub fn is_not_found_path(path: &Path) -> bool {
+    path.parent().map(|x| x.as_os_str().is_empty()).unwrap_or(true)
+    && path.file_stem().map(|x| x == NOT_FOUND_FILE_STEM).unwrap_or(false)
+}
+
+#[cfg(test)]
+impl SitePage {
+
+    /// Create a page from an input relative path and a JSON object state,
+    /// with the output path as the input path with "html", such as for tests.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let page = SitePage::from_json("posts/alfa.md", json!({"title": "Alfa"}));
+    /// ```
+    ///
+    pub fn from_json(path: &str, value: serde_json::Value) -> Self {
+        let state: crate::state::state_with_json::StateWithJSON = value.as_object().expect("object").to_owned();
+        SitePage {
+            input: PathBuf::from(path),
+            output: PathBuf::from(path).with_extension("html"),
+            path: PathBuf::from(path),
+            html: String::new(),
+            text: String::new(),
+            headings: List::new(),
+            template_name: None,
+            items: None,
+            state: Box::new(state),
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_state_value() {
+        let page = SitePage::from_json("alfa.md", json!({"title": "alfa", "content": "<p>bravo</p>", "toc": []}));
+        assert_eq!(page.to_state_value(), StateValue::from(json!({"title": "alfa"})));
+    }
+
+    #[test]
+    fn test_is_published() {
+        let page = |value: serde_json::Value| SitePage::from_json("alfa.md", value);
+        let now = from_date_str_into_system_time("2026-06-01").expect("now");
+        assert!(page(json!({"date": "2026-01-02"})).is_published(false, false, now));
+        let draft = page(json!({"draft": true}));
+        assert!(!draft.is_published(false, false, now));
+        assert!(draft.is_published(true, false, now));
+        let future = page(json!({"date": "2026-01-02", "publish_date": "2026-07-01"}));
+        assert!(!future.is_published(false, false, now));
+        assert!(future.is_published(false, true, now));
+        assert!(!page(json!({"date": "2026-07-01"})).is_published(false, false, now));
+        let expired = page(json!({"expiry_date": "2026-06-01"}));
+        assert!(!expired.is_published(true, true, now));
+        assert!(page(json!({"expiry_date": "2026-06-02"})).is_published(false, false, now));
+    }
+
+    #[test]
+    fn test_is_section_index_path() {
+        assert!(is_section_index_path(Path::new("posts/_index.md")));
+        assert!(!is_section_index_path(Path::new("posts/index.md")));
+        assert!(!is_section_index_path(Path::new("posts/alfa.md")));
+    }
+
+    #[test]
+    fn test_is_not_found_path() {
+        assert!(is_not_found_path(Path::new("404.md")));
+        assert!(is_not_found_path(Path::new("404.html")));
+        assert!(!is_not_found_path(Path::new("posts/404.md")));
+        assert!(!is_not_found_path(Path::new("alfa.md")));
+    }
+
+}
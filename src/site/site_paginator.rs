@@ -0,0 +1,163 @@
+//! Site paginator, i.e. a way to split a listing page's items into chunks.
+//!
+//! A listing page has items, such as a section index page with its section
+//! pages, a term page with its term pages, or a taxonomy page with its terms.
+//!
+//! When a listing page has a `paginate_by` number, from its front matter or
+//! from the option `--paginate-by`, then its items are split into chunks.
+//! The first chunk renders at the page's own URL, such as "/posts/",
+//! and each next chunk renders at a numbered URL, such as "/posts/page/2/".
+//!
+//! Each chunk gets the variable `paginator` with:
+//!
+//! * `items`: the chunk's items.
+//!
+//! * `current`: the chunk number, starting at 1.
+//!
+//! * `total`: the number of chunks, which is at least 1.
+//!
+//! * `prev_url`, `next_url`: the URLs of the nearby chunks, or null.
+
+use std::path::PathBuf;
+use crate::types::list::*;
+use crate::state::state_trait::StateTrait;
+use crate::state::state_value::StateValue;
+use crate::state::state_with_json::StateWithJSON;
+use crate::site::site_page::SitePage;
+
+/// The front matter key that sets how many items per chunk.
+pub const PAGINATE_BY_KEY: &str = "paginate_by";
+
+/// The URL path segment before a chunk number, such as "/posts/page/2/".
+pub const PAGINATE_PATH_AS_STR: &str = "page";
+
+/// Paginate a listing page, if it has items and a paginate by number.
+///
+/// This inserts the `paginator` variable into the page, for the first chunk,
+/// and returns a new page for each next chunk.
+///
+/// Example:
+///
+/// ```rust
+/// let next_pages: List<SitePage> = paginate_site_page(&mut page, Some(10));
+/// ```
+///
+pub fn paginate_site_page(page: &mut SitePage, paginate_by: Option<usize>) -> List<SitePage> {
+    let Some(items) = &page.items else { return List::new() };
+    let paginate_by = page.state.get_value(PAGINATE_BY_KEY)
+        .and_then(|x| x.to_plain_string().trim().parse::<usize>().ok())
+        .or(paginate_by)
+        .filter(|&x| x > 0);
+    let Some(paginate_by) = paginate_by else { return List::new() };
+    trace!("paginate_site_page ➡ output: {:?}, paginate_by: {:?}", page.output, paginate_by);
+    let chunks: List<List<StateValue>> = match items.is_empty() {
+        true => vec![List::new()],
+        false => items.chunks(paginate_by).map(|x| x.to_vec()).collect(),
+    };
+    let total = chunks.len();
+    let url = page.state.get_value("url").map(|x| x.to_plain_string()).unwrap_or_default();
+    let urls: List<String> = (1..=total).map(|n| from_url_and_number_into_url_string(&url, n)).collect();
+    let mut next_pages: List<SitePage> = List::new();
+    for (i, items) in chunks.into_iter().enumerate() {
+        let paginator = StateValue::Map(map!(
+            String::from("items") => StateValue::List(items),
+            String::from("current") => StateValue::Int(i as i64 + 1),
+            String::from("total") => StateValue::Int(total as i64),
+            String::from("prev_url") => if i > 0 { StateValue::from(urls[i - 1].as_str()) } else { StateValue::Null },
+            String::from("next_url") => if i + 1 < total { StateValue::from(urls[i + 1].as_str()) } else { StateValue::Null }
+        ));
+        if i == 0 {
+            page.state.insert_value(String::from("paginator"), paginator);
+            continue;
+        }
+        let mut state = StateWithJSON::new();
+        state.deep_merge(page.state.as_ref());
+        state.insert_value(String::from("url"), StateValue::from(urls[i].as_str()));
+        state.insert_value(String::from("paginator"), paginator);
+        next_pages.push(SitePage {
+            input: page.input.to_owned(),
+            output: from_output_and_number_into_path_buf(&page.output, i + 1),
+            path: page.path.to_owned(),
+            html: page.html.to_owned(),
+            text: String::new(),
+            headings: List::new(),
+            template_name: page.template_name.to_owned(),
+            items: None,
+            state: Box::new(state),
+        });
+    }
+    next_pages
+}
+
+/// Convert from a listing URL and a chunk number into the chunk URL.
+///
+/// Example:
+///
+/// ```rust
+/// assert_eq!(from_url_and_number_into_url_string("/posts/", 1), "/posts/");
+/// assert_eq!(from_url_and_number_into_url_string("/posts/", 2), "/posts/page/2/");
+/// ```
+///
+pub fn from_url_and_number_into_url_string(url: &str, number: usize) -> String {
+    if number <= 1 { return String::from(url) }
+    let dir = match url.rfind('/') {
+        Some(i) => &url[..=i],
+        None => "/",
+    };
+    format!("{}{}/{}/", dir, PAGINATE_PATH_AS_STR, number)
+}
+
+/// Convert from a listing output path and a chunk number into the chunk output path,
+/// such as "posts/index.html" and 2 into "posts/page/2/index.html".
+fn from_output_and_number_into_path_buf(output: &std::path::Path, number: usize) -> PathBuf {
+    let dir = output.parent().map(|x| x.to_path_buf()).unwrap_or_default();
+    let file_name = output.file_name().map(|x| x.to_os_string()).unwrap_or_default();
+    dir.join(PAGINATE_PATH_AS_STR).join(number.to_string()).join(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn listing(value: serde_json::Value, items: usize) -> SitePage {
+        SitePage {
+            output: PathBuf::from("out/posts/index.html"),
+            items: Some((1..=items).map(|x| StateValue::Int(x as i64)).collect()),
+            ..SitePage::from_json("posts/_index.md", value)
+        }
+    }
+
+    #[test]
+    fn test_paginate_site_page() {
+        let mut page = listing(json!({"url": "/posts/", "paginate_by": 2}), 5);
+        let next_pages = paginate_site_page(&mut page, Some(10));
+        assert_eq!(page.state.get_value("paginator"), Some(StateValue::from(json!({
+            "items": [1, 2], "current": 1, "total": 3, "prev_url": null, "next_url": "/posts/page/2/",
+        }))));
+        assert_eq!(next_pages.len(), 2);
+        assert_eq!(next_pages[0].output, PathBuf::from("out/posts/page/2/index.html"));
+        assert_eq!(next_pages[0].state.get_value("url"), Some(StateValue::from("/posts/page/2/")));
+        assert_eq!(next_pages[1].state.get_value("paginator"), Some(StateValue::from(json!({
+            "items": [5], "current": 3, "total": 3, "prev_url": "/posts/page/2/", "next_url": null,
+        }))));
+    }
+
+    #[test]
+    fn test_paginate_site_page_x_default_and_none() {
+        let mut page = listing(json!({"url": "/posts/"}), 3);
+        assert_eq!(paginate_site_page(&mut page, Some(3)).len(), 0);
+        assert!(matches!(page.state.get_value("paginator"), Some(StateValue::Map(_))));
+        let mut page = listing(json!({"url": "/posts/"}), 3);
+        assert_eq!(paginate_site_page(&mut page, None).len(), 0);
+        assert_eq!(page.state.get_value("paginator"), None);
+    }
+
+    #[test]
+    fn test_from_url_and_number_into_url_string() {
+        assert_eq!(from_url_and_number_into_url_string("/posts/", 1), "/posts/");
+        assert_eq!(from_url_and_number_into_url_string("/posts/", 2), "/posts/page/2/");
+        assert_eq!(from_url_and_number_into_url_string("/", 3), "/page/3/");
+    }
+
+}
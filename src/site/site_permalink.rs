@@ -0,0 +1,149 @@
+//! Site permalink, i.e. a page URL from a pattern, rather than from its input path.
+//!
+//! The option `--permalink SECTION PATTERN` sets the pattern for the pages
+//! of a section directory and its subsections, such as:
+//!
+//! ```sh
+//! sita --permalink posts "/blog/:year/:month/:slug/"
+//! ```
+//!
+//! The pattern tokens are:
+//!
+//! * `:year`, `:month`, `:day`: the front matter `date` as written, such as
+//!   "2026", "10", "18", so a date with a UTC offset, such as
+//!   "2026-10-31T22:00:00-05:00", keeps its own day, rather than UTC's.
+//!
+//! * `:slug`: the page variable `slug`, such as "hello-world".
+//!
+//! * `:title`: the page variable `title` as a slug, such as "hello-world".
+//!
+//! A page's front matter `url` wins over any pattern. A URL that ends with
+//! a slash, or has no file extension, outputs to its directory index file,
+//! such as "blog/2026/10/hello-world/index.html".
+
+use std::path::Path;
+use crate::types::map::*;
+use crate::state::state_trait::StateTrait;
+use crate::f::from_date_str_into_system_time::*;
+use crate::f::from_str_into_slug_string::*;
+use crate::site::site_language::is_default_language;
+
+/// Get the permalink pattern of a section directory, such as "posts/2026",
+/// which is the pattern of the directory or its nearest ancestor, if any.
+///
+/// The map keys are section directories, such as "posts" or "/posts/",
+/// and "" or "/" is the root directory.
+pub fn from_permalink_map_and_dir_into_pattern<'a>(permalink_map: &'a Map<String, String>, dir: &Path) -> Option<&'a str> {
+    dir.ancestors().find_map(|ancestor| {
+        permalink_map.iter()
+            .find(|(section, _)| Path::new(section.trim_matches('/')) == ancestor)
+            .map(|(_, pattern)| pattern.as_str())
+    })
+}
+
+/// Get the permalink pattern of a section directory for a language, which
+/// is in the language's own output root, such as "/fr/blog/:slug/" for the
+/// pattern "/blog/:slug/", unless the language is the default language.
+///
+/// Example:
+///
+/// ```rust
+/// let languages = [String::from("en"), String::from("fr")];
+/// from_permalink_map_and_dir_and_language_into_pattern(&permalink_map, Path::new("posts"), "fr", &languages);
+/// //=> Some("/fr/blog/:slug/")
+/// ```
+///
+pub fn from_permalink_map_and_dir_and_language_into_pattern(permalink_map: &Map<String, String>, dir: &Path, language: &str, languages: &[String]) -> Option<String> {
+    let pattern = from_permalink_map_and_dir_into_pattern(permalink_map, dir)?;
+    match is_default_language(language, languages) {
+        true => Some(String::from(pattern)),
+        false => Some(format!("/{}/{}", language, pattern.trim_start_matches('/'))),
+    }
+}
+
+/// Convert from a permalink pattern and a page state into a root-relative URL String.
+///
+/// A token without a value, such as `:year` for a page without a date,
+/// is empty, and any repeated slashes collapse into one.
+///
+/// Example:
+///
+/// ```rust
+/// let url = from_pattern_and_state_into_url_string("/blog/:year/:month/:slug/", state);
+/// //=> "/blog/2026/10/hello-world/"
+/// ```
+///
+pub fn from_pattern_and_state_into_url_string(pattern: &str, state: &dyn StateTrait) -> String {
+    // A valid date starts with "YYYY-MM-DD", so the tokens are its slices.
+    let date = state.get_value("date")
+        .map(|x| x.to_plain_string().trim().to_owned())
+        .filter(|x| from_date_str_into_system_time(x).is_some())
+        .unwrap_or_default();
+    let part = |range: std::ops::Range<usize>| date.get(range).unwrap_or_default().to_owned();
+    let slug = state.get_value("slug").map(|x| x.to_plain_string()).unwrap_or_default();
+    let title = state.get_value("title").map(|x| from_str_into_slug_string(&x.to_plain_string())).unwrap_or_default();
+    let url = pattern
+        .replace(":year", &part(0..4))
+        .replace(":month", &part(5..7))
+        .replace(":day", &part(8..10))
+        .replace(":slug", &slug)
+        .replace(":title", &title);
+    let mut s = String::from("/");
+    for c in url.chars() {
+        if !(c == '/' && s.ends_with('/')) {
+            s.push(c);
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::state::state_with_json::StateWithJSON;
+
+    #[test]
+    fn test_from_permalink_map_and_dir_into_pattern() {
+        let permalink_map = map!(
+            String::from("/posts/") => String::from("/blog/:slug/"),
+            String::from("posts/news") => String::from("/news/:slug/")
+        );
+        assert_eq!(from_permalink_map_and_dir_into_pattern(&permalink_map, Path::new("posts")), Some("/blog/:slug/"));
+        assert_eq!(from_permalink_map_and_dir_into_pattern(&permalink_map, Path::new("posts/2026")), Some("/blog/:slug/"));
+        assert_eq!(from_permalink_map_and_dir_into_pattern(&permalink_map, Path::new("posts/news")), Some("/news/:slug/"));
+        assert_eq!(from_permalink_map_and_dir_into_pattern(&permalink_map, Path::new("about")), None);
+        assert_eq!(from_permalink_map_and_dir_into_pattern(&permalink_map, Path::new("")), None);
+        let permalink_map = map!(String::from("/") => String::from("/:slug/"));
+        assert_eq!(from_permalink_map_and_dir_into_pattern(&permalink_map, Path::new("about")), Some("/:slug/"));
+    }
+
+    #[test]
+    fn test_from_permalink_map_and_dir_and_language_into_pattern() {
+        let permalink_map = map!(String::from("posts") => String::from("/blog/:slug/"));
+        let languages = [String::from("en"), String::from("fr")];
+        let f = |dir: &str, language: &str| from_permalink_map_and_dir_and_language_into_pattern(&permalink_map, Path::new(dir), language, &languages);
+        assert_eq!(f("posts", "en"), Some(String::from("/blog/:slug/")));
+        assert_eq!(f("posts", "fr"), Some(String::from("/fr/blog/:slug/")));
+        assert_eq!(f("about", "fr"), None);
+        assert_eq!(from_permalink_map_and_dir_and_language_into_pattern(&permalink_map, Path::new("posts"), "", &[]), Some(String::from("/blog/:slug/")));
+    }
+
+    #[test]
+    fn test_from_pattern_and_state_into_url_string() {
+        let state: StateWithJSON = json!({"date": "2026-10-18", "slug": "alfa", "title": "Bravo Charlie"}).as_object().expect("object").to_owned();
+        assert_eq!(from_pattern_and_state_into_url_string("/blog/:year/:month/:day/:slug/", &state), "/blog/2026/10/18/alfa/");
+        assert_eq!(from_pattern_and_state_into_url_string("blog/:title.html", &state), "/blog/bravo-charlie.html");
+        let state: StateWithJSON = json!({"slug": "alfa"}).as_object().expect("object").to_owned();
+        assert_eq!(from_pattern_and_state_into_url_string("/blog/:year/:month/:slug/", &state), "/blog/alfa/");
+    }
+
+    #[test]
+    fn test_from_pattern_and_state_into_url_string_x_date_with_offset() {
+        let state: StateWithJSON = json!({"date": "2026-10-31T22:00:00-05:00", "slug": "alfa"}).as_object().expect("object").to_owned();
+        assert_eq!(from_pattern_and_state_into_url_string("/:year/:month/:day/:slug/", &state), "/2026/10/31/alfa/");
+        let state: StateWithJSON = json!({"date": "2026-13-01", "slug": "alfa"}).as_object().expect("object").to_owned();
+        assert_eq!(from_pattern_and_state_into_url_string("/:year/:month/:slug/", &state), "/alfa/");
+    }
+
+}
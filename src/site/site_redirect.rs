@@ -0,0 +1,146 @@
+//! Site redirect, i.e. an old URL that redirects to a page's current URL.
+//!
+//! A page's front matter `aliases` lists its old URLs, such as
+//! `aliases: [/old/path/]`, and the option `--redirect FROM TO` adds
+//! site-wide redirects, such as for a moved section.
+//!
+//! For each old URL, Sita writes a small HTML page with a meta refresh
+//! and a canonical link, such as `old/path/index.html`, which works on
+//! any static host. Sita can also write a `_redirects` file, which hosts
+//! such as Netlify and Cloudflare Pages serve as HTTP redirects.
+
+use std::path::{Path, PathBuf};
+use crate::types::list::*;
+use crate::f::from_base_url_and_url_into_absolute_url_string::*;
+use crate::f::from_str_into_html_escape_string::*;
+use crate::f::from_url_into_relative_path_buf::*;
+
+/// The redirects file name, for hosts such as Netlify and Cloudflare Pages.
+pub const REDIRECTS_FILE_NAME_AS_STR: &str = "_redirects";
+
+/// The redirects file status code, i.e. moved permanently.
+pub const REDIRECTS_STATUS_CODE: u16 = 301;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteRedirect {
+
+    /// The old root-relative URL, such as "/old/path/".
+    pub from: String,
+
+    /// The new root-relative URL, such as "/posts/alfa.html",
+    /// or an absolute URL, such as "https://example.com/".
+    pub to: String,
+
+}
+
+impl SiteRedirect {
+
+    /// Create a redirect, with the old URL made root-relative, such as "old/" into "/old/".
+    pub fn new(from: &str, to: &str) -> Self {
+        let from = match from.starts_with('/') {
+            true => String::from(from),
+            false => format!("/{}", from),
+        };
+        Self { from, to: String::from(to) }
+    }
+
+    /// Get the output path relative to the output directory, if any,
+    /// such as "/old/path/" into "old/path/index.html",
+    /// and "/old.html" into "old.html".
+    ///
+    /// A URL without a file extension is a directory, so gets its index file,
+    /// with the output file name extension, such as "html" or "htm".
+    /// A URL that escapes the output directory, such as "/../x", has none.
+    pub fn output_path(&self, extension: &Path) -> Option<PathBuf> {
+        from_url_into_relative_path_buf(&self.from, extension)
+    }
+
+    /// Convert to an HTML String, with a meta refresh and a canonical link to the new URL.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let html = SiteRedirect::new("/old/", "/new/").to_html_string(Some("https://example.com"));
+    /// //=> "<!DOCTYPE html>\n<html>\n<head>\n…<link rel=\"canonical\" href=\"https://example.com/new/\">…"
+    /// ```
+    ///
+    pub fn to_html_string(&self, base_url: Option<&str>) -> String {
+        let url = from_str_into_html_escape_string(&self.to_url(base_url));
+        format!(
+            concat!(
+                "<!DOCTYPE html>\n",
+                "<html>\n",
+                "<head>\n",
+                "<meta charset=\"utf-8\">\n",
+                "<title>{url}</title>\n",
+                "<link rel=\"canonical\" href=\"{url}\">\n",
+                "<meta name=\"robots\" content=\"noindex\">\n",
+                "<meta http-equiv=\"refresh\" content=\"0; url={url}\">\n",
+                "</head>\n",
+                "<body>\n",
+                "<p><a href=\"{url}\">{url}</a></p>\n",
+                "</body>\n",
+                "</html>\n",
+            ),
+            url = url
+        )
+    }
+
+    /// Get the new URL, made absolute via the base URL, if any.
+    fn to_url(&self, base_url: Option<&str>) -> String {
+        match self.to.starts_with('/') {
+            true => from_base_url_and_url_into_absolute_url_string(base_url, &self.to),
+            false => self.to.to_owned(),
+        }
+    }
+
+}
+
+/// Convert redirects into a `_redirects` String, with one rule per line,
+/// i.e. the old URL, the new URL, and the status code.
+///
+/// Example:
+///
+/// ```rust
+/// let s = to_redirects_string(&[SiteRedirect::new("/old/", "/new/")]);
+/// //=> "/old/ /new/ 301\n"
+/// ```
+///
+pub fn to_redirects_string(redirects: &[SiteRedirect]) -> String {
+    redirects.iter()
+        .map(|x| format!("{} {} {}\n", x.from, x.to, REDIRECTS_STATUS_CODE))
+        .collect::<List<String>>()
+        .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_path() {
+        let html = Path::new("html");
+        assert_eq!(SiteRedirect::new("/old/path/", "/").output_path(html), Some(PathBuf::from("old/path/index.html")));
+        assert_eq!(SiteRedirect::new("old/path", "/").output_path(html), Some(PathBuf::from("old/path/index.html")));
+        assert_eq!(SiteRedirect::new("/old.html?x=1", "/").output_path(html), Some(PathBuf::from("old.html")));
+        assert_eq!(SiteRedirect::new("/", "/new/").output_path(html), Some(PathBuf::from("index.html")));
+        assert_eq!(SiteRedirect::new("/old/path/", "/").output_path(Path::new("htm")), Some(PathBuf::from("old/path/index.htm")));
+        assert_eq!(SiteRedirect::new("/../x/", "/").output_path(html), None);
+    }
+
+    #[test]
+    fn test_to_html_string() {
+        let html = SiteRedirect::new("/old/", "/new/?a=1&b=2").to_html_string(Some("https://example.com"));
+        assert!(html.contains(r#"<link rel="canonical" href="https://example.com/new/?a=1&amp;b=2">"#));
+        assert!(html.contains(r#"<meta http-equiv="refresh" content="0; url=https://example.com/new/?a=1&amp;b=2">"#));
+        let html = SiteRedirect::new("/old/", "https://example.org/").to_html_string(Some("https://example.com"));
+        assert!(html.contains(r#"<link rel="canonical" href="https://example.org/">"#));
+    }
+
+    #[test]
+    fn test_to_redirects_string() {
+        let redirects = [SiteRedirect::new("/old/", "/new/"), SiteRedirect::new("/a.html", "/b.html")];
+        assert_eq!(to_redirects_string(&redirects), "/old/ /new/ 301\n/a.html /b.html 301\n");
+    }
+
+}
@@ -0,0 +1,174 @@
+//! Site search, i.e. a search index file for client-side search.
+//!
+//! The search index is `search-index.json` in the output directory,
+//! so a browser can search the site offline, without a server.
+//!
+//! The index has `pages`, which lists each page's fields, by default
+//! `url`, `title`, `headings`, and `text`, which is the plain-text content
+//! with stop words removed. Any other field comes from the page state,
+//! such as `description` or `tags`.
+//!
+//! The index can also have `index`, which is a prebuilt inverted index,
+//! i.e. a map from each word to the positions of its pages in `pages`,
+//! so the browser doesn't need to tokenize every page.
+//!
+//! Example:
+//!
+//! ```json
+//! {
+//!   "pages": [{"url": "/alfa.html", "title": "Alfa", "headings": ["Bravo"], "text": "alfa bravo charlie"}],
+//!   "index": {"alfa": [0], "bravo": [0], "charlie": [0]}
+//! }
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+use crate::types::{list::*, map::*, set::*};
+use crate::state::state_value::StateValue;
+use crate::site::site_page::SitePage;
+
+/// The search index file name.
+pub const SEARCH_INDEX_FILE_NAME_AS_STR: &str = "search-index.json";
+
+/// The default fields of each page in the search index.
+pub const SEARCH_FIELDS_DEFAULT: [&str; 4] = ["url", "title", "headings", "text"];
+
+/// The stop words, i.e. common English words that don't help search.
+pub const STOP_WORDS: [&str; 64] = [
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as",
+    "at", "be", "because", "been", "but", "by", "can", "could", "do", "for",
+    "from", "had", "has", "have", "he", "her", "his", "how", "i", "if",
+    "in", "into", "is", "it", "its", "just", "me", "my", "no", "not",
+    "of", "on", "or", "our", "she", "so", "than", "that", "the", "their",
+    "them", "then", "there", "these", "they", "this", "to", "was", "we", "were",
+    "what", "which", "with", "you",
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SiteSearch {
+
+    /// The pages, each as a map of field name to value, in walk order.
+    pub pages: List<Map<String, StateValue>>,
+
+    /// The words of each page, such as for the inverted index, in walk order.
+    pub words: List<Set<String>>,
+
+}
+
+impl SiteSearch {
+
+    /// Create a search index from pages, with each page's fields.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let search = SiteSearch::from_pages(&pages, &[String::from("url"), String::from("title")]);
+    /// ```
+    ///
+    pub fn from_pages(pages: &[&SitePage], fields: &[String]) -> Self {
+        trace!("SiteSearch::from_pages ➡ pages: {:?}, fields: {:?}", pages.len(), fields);
+        let mut search = SiteSearch::default();
+        for page in pages.iter() {
+            let title = page.state.get_value("title").map(|x| x.to_plain_string()).unwrap_or_default();
+            let text = from_str_into_search_words(&page.text).join(" ");
+            let map: Map<String, StateValue> = fields.iter().filter_map(|field| {
+                let value = match field.as_str() {
+                    "headings" => Some(StateValue::List(page.headings.iter().map(|x| StateValue::from(x.as_str())).collect())),
+                    "text" => Some(StateValue::from(text.as_str())),
+                    _ => page.state.get_value(field),
+                };
+                value.map(|x| (field.to_owned(), x))
+            }).collect();
+            let words: Set<String> = from_str_into_search_words(&title).into_iter()
+                .chain(page.headings.iter().flat_map(|x| from_str_into_search_words(x)))
+                .chain(text.split(' ').filter(|x| !x.is_empty()).map(String::from))
+                .collect();
+            search.pages.push(map);
+            search.words.push(words);
+        }
+        search
+    }
+
+    /// Convert to a search index JSON String, with the inverted index if wanted.
+    pub fn to_json_string(&self, inverted_index: bool) -> String {
+        let pages: List<serde_json::Value> = self.pages.iter()
+            .map(|x| serde_json::Value::from(StateValue::Map(x.to_owned())))
+            .collect();
+        let mut value = serde_json::json!({ "pages": pages });
+        if inverted_index {
+            let mut index: Map<&str, List<usize>> = Map::new();
+            for (i, words) in self.words.iter().enumerate() {
+                for word in words.iter() {
+                    index.entry(word.as_str()).or_default().push(i);
+                }
+            }
+            value["index"] = serde_json::json!(index);
+        }
+        serde_json::to_string(&value).unwrap_or_default()
+    }
+
+}
+
+/// Convert from a str into search words, i.e. lowercase words without stop words.
+///
+/// Example:
+///
+/// ```rust
+/// let words = from_str_into_search_words("The Rust book, and the Cargo book");
+/// //=> ["rust", "book", "cargo", "book"]
+/// ```
+///
+pub fn from_str_into_search_words(s: &str) -> List<String> {
+    s.unicode_words()
+        .map(|x| x.to_lowercase())
+        .filter(|x| !STOP_WORDS.contains(&x.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page(value: serde_json::Value, text: &str, headings: &[&str]) -> SitePage {
+        SitePage {
+            text: String::from(text),
+            headings: headings.iter().map(|x| String::from(*x)).collect(),
+            ..SitePage::from_json("alfa.md", value)
+        }
+    }
+
+    #[test]
+    fn test_from_str_into_search_words() {
+        assert_eq!(from_str_into_search_words("The Rust book, and the Cargo book!"), vec!["rust", "book", "cargo", "book"]);
+        assert!(from_str_into_search_words("the and of").is_empty());
+    }
+
+    #[test]
+    fn test_to_json_string() {
+        let pages = [
+            page(json!({"url": "/alfa.html", "title": "Alfa", "tags": ["x"]}), "Alfa is the first. Bravo", &["Bravo"]),
+            page(json!({"url": "/charlie.html", "title": "Charlie"}), "Charlie and bravo", &[]),
+        ];
+        let pages: List<&SitePage> = pages.iter().collect();
+        let fields: List<String> = SEARCH_FIELDS_DEFAULT.iter().map(|x| String::from(*x)).collect();
+        let search = SiteSearch::from_pages(&pages, &fields);
+        let value: serde_json::Value = serde_json::from_str(&search.to_json_string(true)).expect("json");
+        assert_eq!(value, json!({
+            "pages": [
+                {"url": "/alfa.html", "title": "Alfa", "headings": ["Bravo"], "text": "alfa first bravo"},
+                {"url": "/charlie.html", "title": "Charlie", "headings": [], "text": "charlie bravo"},
+            ],
+            "index": {"alfa": [0], "bravo": [0, 1], "charlie": [1], "first": [0]},
+        }));
+    }
+
+    #[test]
+    fn test_to_json_string_x_fields() {
+        let pages = [page(json!({"url": "/alfa.html", "title": "Alfa", "tags": ["x"]}), "Alfa", &[])];
+        let pages: List<&SitePage> = pages.iter().collect();
+        let search = SiteSearch::from_pages(&pages, &[String::from("url"), String::from("tags"), String::from("missing")]);
+        let value: serde_json::Value = serde_json::from_str(&search.to_json_string(false)).expect("json");
+        assert_eq!(value, json!({"pages": [{"url": "/alfa.html", "tags": ["x"]}]}));
+    }
+
+}
@@ -0,0 +1,200 @@
+//! Site sitemap, i.e. every rendered page URL for search engines.
+//!
+//! The sitemap is `sitemap.xml` in the output directory, with each page's
+//! absolute URL and its `lastmod` date, if any. The protocol allows at most
+//! 50,000 URLs per file, so a larger site gets numbered files, such as
+//! `sitemap-1.xml`, and `sitemap.xml` becomes their sitemap index.
+//!
+//! The robots file is `robots.txt` in the output directory, with any
+//! disallow rules, and with a link to the sitemap.
+//!
+//! See <https://www.sitemaps.org/protocol.html>
+
+use std::time::SystemTime;
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use crate::types::list::*;
+use crate::f::from_base_url_and_url_into_absolute_url_string::*;
+use crate::f::from_system_time_into_rfc3339_string::*;
+
+/// The sitemap file name, which is the sitemap index for a large site.
+pub const SITEMAP_FILE_NAME_AS_STR: &str = "sitemap.xml";
+
+/// The most URLs per sitemap file, per the sitemap protocol.
+pub const SITEMAP_URL_LIMIT: usize = 50_000;
+
+/// The robots file name.
+pub const ROBOTS_FILE_NAME_AS_STR: &str = "robots.txt";
+
+/// The sitemaps XML namespace.
+const SITEMAP_XMLNS: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SiteSitemap {
+
+    /// The URLs, in render order.
+    pub urls: List<SiteSitemapUrl>,
+
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteSitemapUrl {
+
+    /// The root-relative URL, such as "/posts/alfa.html".
+    pub url: String,
+
+    /// The last modified time, if any, such as from front matter.
+    pub lastmod: Option<SystemTime>,
+
+}
+
+impl SiteSitemap {
+
+    /// Convert to sitemap files, as pairs of file name and XML String.
+    ///
+    /// Up to the limit of URLs, this is one file `sitemap.xml`.
+    /// Over the limit, this is numbered files, such as `sitemap-1.xml`,
+    /// plus `sitemap.xml` as their sitemap index.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let files = sitemap.to_files(Some("https://example.com"), SITEMAP_URL_LIMIT)?;
+    /// //=> [("sitemap.xml", "<?xml …")]
+    /// ```
+    ///
+    pub fn to_files(&self, base_url: Option<&str>, limit: usize) -> Result<List<(String, String)>, quick_xml::Error> {
+        trace!("SiteSitemap::to_files ➡ urls: {:?}, limit: {:?}", self.urls.len(), limit);
+        if self.urls.len() <= limit {
+            return Ok(vec![(String::from(SITEMAP_FILE_NAME_AS_STR), to_urlset_string(&self.urls, base_url)?)])
+        }
+        let mut files: List<(String, String)> = List::new();
+        let mut index: List<SiteSitemapUrl> = List::new();
+        for (i, urls) in self.urls.chunks(limit.max(1)).enumerate() {
+            let file_name = format!("sitemap-{}.xml", i + 1);
+            index.push(SiteSitemapUrl {
+                url: format!("/{}", file_name),
+                lastmod: urls.iter().filter_map(|x| x.lastmod).max(),
+            });
+            files.push((file_name, to_urlset_string(urls, base_url)?));
+        }
+        files.insert(0, (String::from(SITEMAP_FILE_NAME_AS_STR), to_sitemapindex_string(&index, base_url)?));
+        Ok(files)
+    }
+
+}
+
+/// Convert URLs into a sitemap `urlset` XML String.
+fn to_urlset_string(urls: &[SiteSitemapUrl], base_url: Option<&str>) -> Result<String, quick_xml::Error> {
+    to_xml_string("urlset", "url", urls, base_url)
+}
+
+/// Convert sitemap file URLs into a sitemap index `sitemapindex` XML String.
+fn to_sitemapindex_string(urls: &[SiteSitemapUrl], base_url: Option<&str>) -> Result<String, quick_xml::Error> {
+    to_xml_string("sitemapindex", "sitemap", urls, base_url)
+}
+
+/// Convert URLs into a sitemap XML String, with each item's `loc` and `lastmod`.
+fn to_xml_string(root: &str, item: &str, urls: &[SiteSitemapUrl], base_url: Option<&str>) -> Result<String, quick_xml::Error> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer.create_element(root)
+        .with_attribute(("xmlns", SITEMAP_XMLNS))
+        .write_inner_content(|writer| {
+            for url in urls.iter() {
+                writer.create_element(item).write_inner_content(|writer| {
+                    let loc = from_base_url_and_url_into_absolute_url_string(base_url, &url.url);
+                    writer.create_element("loc").write_text_content(BytesText::new(&loc))?;
+                    if let Some(lastmod) = url.lastmod {
+                        writer.create_element("lastmod").write_text_content(BytesText::new(&from_system_time_into_rfc3339_string(lastmod)))?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+    Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+}
+
+/// Convert into a robots.txt String, with disallow rules for all user agents,
+/// and with a link to the sitemap, if any.
+///
+/// Example:
+///
+/// ```rust
+/// let s = to_robots_string(Some("https://example.com"), &[String::from("/drafts/")], true);
+/// //=> "User-agent: *\nDisallow: /drafts/\n\nSitemap: https://example.com/sitemap.xml\n"
+/// ```
+///
+pub fn to_robots_string(base_url: Option<&str>, disallow_list: &[String], sitemap: bool) -> String {
+    let mut s = String::from("User-agent: *\n");
+    if disallow_list.is_empty() {
+        s.push_str("Disallow:\n");
+    }
+    for disallow in disallow_list.iter() {
+        s.push_str(&format!("Disallow: {}\n", disallow));
+    }
+    if sitemap {
+        let url = from_base_url_and_url_into_absolute_url_string(base_url, &format!("/{}", SITEMAP_FILE_NAME_AS_STR));
+        s.push_str(&format!("\nSitemap: {}\n", url));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use crate::f::from_date_str_into_system_time::*;
+
+    fn sitemap() -> SiteSitemap {
+        SiteSitemap { urls: vec![
+            SiteSitemapUrl { url: String::from("/"), lastmod: None },
+            SiteSitemapUrl { url: String::from("/alfa.html?x=1&y=2"), lastmod: from_date_str_into_system_time("2026-01-02") },
+            SiteSitemapUrl { url: String::from("/bravo.html"), lastmod: from_date_str_into_system_time("2026-03-04") },
+        ]}
+    }
+
+    #[test]
+    fn test_to_files() {
+        let files = sitemap().to_files(Some("https://example.com"), SITEMAP_URL_LIMIT).expect("to_files");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "sitemap.xml");
+        assert_eq!(files[0].1, indoc!{r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+              <url>
+                <loc>https://example.com/</loc>
+              </url>
+              <url>
+                <loc>https://example.com/alfa.html?x=1&amp;y=2</loc>
+                <lastmod>2026-01-02T00:00:00Z</lastmod>
+              </url>
+              <url>
+                <loc>https://example.com/bravo.html</loc>
+                <lastmod>2026-03-04T00:00:00Z</lastmod>
+              </url>
+            </urlset>"#
+        });
+    }
+
+    #[test]
+    fn test_to_files_x_over_limit() {
+        let files = sitemap().to_files(Some("https://example.com"), 2).expect("to_files");
+        assert_eq!(files.iter().map(|x| x.0.as_str()).collect::<List<&str>>(), vec!["sitemap.xml", "sitemap-1.xml", "sitemap-2.xml"]);
+        assert!(files[0].1.contains("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">"));
+        assert!(files[0].1.contains("<loc>https://example.com/sitemap-2.xml</loc>\n    <lastmod>2026-03-04T00:00:00Z</lastmod>"));
+        assert!(files[2].1.contains("<loc>https://example.com/bravo.html</loc>"));
+        assert!(!files[2].1.contains("alfa.html"));
+    }
+
+    #[test]
+    fn test_to_robots_string() {
+        assert_eq!(
+            to_robots_string(Some("https://example.com"), &[String::from("/drafts/"), String::from("/tmp/")], true),
+            "User-agent: *\nDisallow: /drafts/\nDisallow: /tmp/\n\nSitemap: https://example.com/sitemap.xml\n"
+        );
+        assert_eq!(to_robots_string(None, &[], false), "User-agent: *\nDisallow:\n");
+    }
+
+}
@@ -0,0 +1,195 @@
+//! Site taxonomy, i.e. a way to group pages by terms, such as tags or categories.
+//!
+//! A page sets its terms in its front matter, by the taxonomy name,
+//! as a list such as `tags: [rust, web]` or as a comma-separated string.
+//!
+//! Each taxonomy gets generated listing pages:
+//!
+//! * A taxonomy page, such as `/tags/`, with the variable `taxonomy`
+//!   that has `name`, `title`, `url`, and `terms`.
+//!
+//! * A term page per term, such as `/tags/rust/`, with the variable `term`
+//!   that has `name`, `slug`, `url`, and `pages`, and with `taxonomy`.
+//!
+//! Each page gets the variable `taxonomies`, which maps each taxonomy name
+//! to the page's term links, such as `taxonomies.tags` with `name` and `url`.
+
+use std::path::{Path, PathBuf};
+use crate::types::list::*;
+use crate::state::state_value::StateValue;
+use crate::f::from_relative_path_into_url_string::*;
+use crate::f::from_str_into_slug_string::*;
+use crate::f::from_str_into_title_string::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteTaxonomy {
+
+    /// The taxonomy name, which is also its front matter key, such as "tags".
+    pub name: String,
+
+    /// The terms, sorted by name case-insensitively.
+    pub terms: List<SiteTerm>,
+
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteTerm {
+
+    /// The term name as first written, such as "Rust".
+    pub name: String,
+
+    /// The term slug, such as "rust".
+    pub slug: String,
+
+    /// The indexes of the term's pages in `SiteModel::pages`, in walk order.
+    pub page_indexes: List<usize>,
+
+}
+
+impl SiteTaxonomy {
+
+    /// Get the output path relative to the output directory, such as "tags/index.html".
+    pub fn output_path(&self, extension: &Path) -> PathBuf {
+        PathBuf::from(from_str_into_slug_string(&self.name)).join(INDEX_FILE_NAME_AS_STR).with_extension(extension)
+    }
+
+    /// Get the root-relative URL, such as "/tags/".
+    pub fn url(&self) -> String {
+        from_relative_path_into_url_string(&PathBuf::from(from_str_into_slug_string(&self.name)).join(INDEX_FILE_NAME_AS_STR))
+    }
+
+    /// Get the term output path relative to the output directory, such as "tags/rust/index.html".
+    pub fn term_output_path(&self, term: &SiteTerm, extension: &Path) -> PathBuf {
+        PathBuf::from(from_str_into_slug_string(&self.name)).join(&term.slug).join(INDEX_FILE_NAME_AS_STR).with_extension(extension)
+    }
+
+    /// Get the term root-relative URL, such as "/tags/rust/".
+    pub fn term_url(&self, term: &SiteTerm) -> String {
+        from_relative_path_into_url_string(&PathBuf::from(from_str_into_slug_string(&self.name)).join(&term.slug).join(INDEX_FILE_NAME_AS_STR))
+    }
+
+    /// Convert to a state value, with each term's name, slug, and URL.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let value = taxonomy.to_state_value();
+    /// //=> {"name": "tags", "title": "Tags", "url": "/tags/", "terms": [{"name": "Rust", "slug": "rust", "url": "/tags/rust/"}]}
+    /// ```
+    ///
+    pub fn to_state_value(&self) -> StateValue {
+        StateValue::Map(map!(
+            String::from("name") => StateValue::from(self.name.as_str()),
+            String::from("title") => StateValue::from(from_str_into_title_string(&self.name)),
+            String::from("url") => StateValue::from(self.url()),
+            String::from("terms") => StateValue::List(self.terms.iter().map(|x| self.term_link_state_value(x)).collect())
+        ))
+    }
+
+    /// Convert a term to a state value link, with its name, slug, and URL.
+    pub fn term_link_state_value(&self, term: &SiteTerm) -> StateValue {
+        StateValue::Map(map!(
+            String::from("name") => StateValue::from(term.name.as_str()),
+            String::from("slug") => StateValue::from(term.slug.as_str()),
+            String::from("url") => StateValue::from(self.term_url(term))
+        ))
+    }
+
+}
+
+/// Convert from a front matter value into term names.
+///
+/// A list becomes its items, and a string becomes its comma-separated parts.
+/// Each name is trimmed, and any empty name is skipped.
+///
+/// Example:
+///
+/// ```rust
+/// let names = from_state_value_into_term_names(&StateValue::from("rust, web"));
+/// //=> ["rust", "web"]
+/// ```
+///
+pub fn from_state_value_into_term_names(value: &StateValue) -> List<String> {
+    let names: List<String> = match value {
+        StateValue::List(x) => x.iter().map(StateValue::to_plain_string).collect(),
+        StateValue::String(x) => x.split(',').map(String::from).collect(),
+        StateValue::Null => List::new(),
+        x => vec![x.to_plain_string()],
+    };
+    names.into_iter()
+        .map(|x| String::from(x.trim()))
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+/// Build a taxonomy from each page's front matter value, if any, in walk order.
+///
+/// Terms are matched by slug, so "Rust" and "rust" are the same term,
+/// which keeps the name as first written.
+///
+/// Example:
+///
+/// ```rust
+/// let taxonomy = from_values_into_site_taxonomy("tags", &[Some(StateValue::from("rust")), None]);
+/// //=> SiteTaxonomy { name: "tags", terms: [SiteTerm { name: "rust", slug: "rust", page_indexes: [0] }] }
+/// ```
+///
+pub fn from_values_into_site_taxonomy(name: &str, values: &[Option<StateValue>]) -> SiteTaxonomy {
+    let mut terms: List<SiteTerm> = List::new();
+    for (index, value) in values.iter().enumerate() {
+        let Some(value) = value else { continue };
+        for term_name in from_state_value_into_term_names(value) {
+            let slug = from_str_into_slug_string(&term_name);
+            if slug.is_empty() { continue; }
+            match terms.iter_mut().find(|x| x.slug == slug) {
+                Some(term) => if !term.page_indexes.contains(&index) { term.page_indexes.push(index) },
+                None => terms.push(SiteTerm { name: term_name, slug, page_indexes: vec![index] }),
+            }
+        }
+    }
+    terms.sort_by_cached_key(|x| x.name.to_lowercase());
+    SiteTaxonomy { name: String::from(name), terms }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_state_value_into_term_names() {
+        assert_eq!(from_state_value_into_term_names(&StateValue::from(json!(["alfa", " bravo ", ""]))), vec!["alfa", "bravo"]);
+        assert_eq!(from_state_value_into_term_names(&StateValue::from("alfa, bravo,")), vec!["alfa", "bravo"]);
+        assert_eq!(from_state_value_into_term_names(&StateValue::Int(2026)), vec!["2026"]);
+        assert!(from_state_value_into_term_names(&StateValue::Null).is_empty());
+    }
+
+    #[test]
+    fn test_from_values_into_site_taxonomy() {
+        let values = [
+            Some(StateValue::from(json!(["Rust", "web"]))),
+            None,
+            Some(StateValue::from("rust, Apps")),
+        ];
+        let taxonomy = from_values_into_site_taxonomy("tags", &values);
+        assert_eq!(taxonomy.terms, vec![
+            SiteTerm { name: String::from("Apps"), slug: String::from("apps"), page_indexes: vec![2] },
+            SiteTerm { name: String::from("Rust"), slug: String::from("rust"), page_indexes: vec![0, 2] },
+            SiteTerm { name: String::from("web"), slug: String::from("web"), page_indexes: vec![0] },
+        ]);
+        assert_eq!(taxonomy.url(), "/tags/");
+        assert_eq!(taxonomy.term_url(&taxonomy.terms[1]), "/tags/rust/");
+        assert_eq!(taxonomy.term_output_path(&taxonomy.terms[1], Path::new("html")), PathBuf::from("tags/rust/index.html"));
+        assert_eq!(taxonomy.to_state_value(), StateValue::from(json!({
+            "name": "tags",
+            "title": "Tags",
+            "url": "/tags/",
+            "terms": [
+                {"name": "Apps", "slug": "apps", "url": "/tags/apps/"},
+                {"name": "Rust", "slug": "rust", "url": "/tags/rust/"},
+                {"name": "web", "slug": "web", "url": "/tags/web/"},
+            ],
+        })));
+    }
+
+}
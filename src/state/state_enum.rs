@@ -8,11 +8,40 @@
 //! * YAML: Yet Anther Markup Language
 //!
 //! This can be expanded for potential future formats.
+//!
+//! The enum serializes untagged, i.e. as its inner state, so any serde
+//! consumer such as a schema checker can read any flavor the same way.
+
+use serde::Serialize;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
 pub enum StateEnum {
     StateWithMap(crate::state::state_with_map::StateWithMap),
     StateWithJSON(crate::state::state_with_json::StateWithJSON),
     StateWithTOML(crate::state::state_with_toml::StateWithTOML),
     StateWithYAML(crate::state::state_with_yaml::StateWithYAML),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_x_map() {
+        let state_enum = StateEnum::StateWithMap(map!(
+            String::from("alfa") => String::from("bravo")
+        ));
+        let actual = serde_json::to_value(&state_enum).expect("to_value");
+        assert_eq!(actual, serde_json::json!({"alfa": "bravo"}));
+    }
+
+    #[test]
+    fn test_serialize_x_toml() {
+        let table: crate::state::state_with_toml::StateWithTOML = toml::from_str("alfa = 1").expect("from_str");
+        let state_enum = StateEnum::StateWithTOML(table);
+        let actual = serde_json::to_value(&state_enum).expect("to_value");
+        assert_eq!(actual, serde_json::json!({"alfa": 1}));
+    }
+
+}
@@ -6,6 +6,7 @@
 
 use std::any::Any;
 use crate::state::state_enum::StateEnum;
+use crate::state::state_value::StateValue;
 
 pub trait StateTrait: std::fmt::Debug {
 
@@ -22,8 +23,71 @@ pub trait StateTrait: std::fmt::Debug {
     /// Insert the key and value.
     fn insert(&mut self, key: String, value: String) -> ();
 
-    /// If the key doesn't exist then insert the key and value.
+    /// Get the value of the key, if any.
+    fn get_value(&self, key: &str) -> Option<StateValue>;
+
+    /// Remove the key, and return its value, if any.
     #[allow(dead_code)]
+    fn remove_value(&mut self, key: &str) -> Option<StateValue>;
+
+    /// Insert the key and typed value.
+    fn insert_value(&mut self, key: String, value: StateValue);
+
+    /// Insert the typed value at a dotted key path, such as "site.author.name".
+    ///
+    /// This creates any missing maps along the path, and deep merges
+    /// into any existing map, so sibling keys are kept.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// state.insert_value_at_key_path("site.author.name", StateValue::from("Ada"));
+    /// //=> {"site": {"author": {"name": "Ada"}}}
+    /// ```
+    ///
+    fn insert_value_at_key_path(&mut self, key_path: &str, value: StateValue) {
+        let mut keys: Vec<&str> = key_path.split('.').collect();
+        let key = String::from(keys.remove(0));
+        let nested = keys.into_iter().rev().fold(value, |acc, x| {
+            StateValue::Map(map!(String::from(x) => acc))
+        });
+        match (self.get_value(&key), nested) {
+            (Some(mut x @ StateValue::Map(_)), nested @ StateValue::Map(_)) => {
+                x.deep_merge(nested);
+                self.insert_value(key, x);
+            },
+            (_, nested) => self.insert_value(key, nested),
+        }
+    }
+
+    /// Iterate on each key and value.
+    fn iter_values(&self) -> Box<dyn Iterator<Item = (String, StateValue)> + '_>;
+
+    /// Deep merge another state of any flavor into this state.
+    ///
+    /// When both values of a key are maps, then merge them recursively.
+    /// Otherwise the other state's value wins.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let mut state: StateWithJSON = …;
+    /// state.deep_merge(&front_matter_state_with_yaml);
+    /// ```
+    ///
+    fn deep_merge(&mut self, other: &dyn StateTrait) {
+        for (key, value) in other.iter_values() {
+            match self.get_value(&key) {
+                Some(mut x @ StateValue::Map(_)) => {
+                    x.deep_merge(value);
+                    self.insert_value(key, x);
+                },
+                _ => self.insert_value(key, value),
+            }
+        }
+    }
+
+    /// If the key doesn't exist then insert the key and value.
     fn contains_key_or_insert(&mut self, key: String, value: String) -> () {
         if !self.contains_key(&key) {
             self.insert(key, value);
@@ -31,3 +95,63 @@ pub trait StateTrait: std::fmt::Debug {
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::state::state_with_json::StateWithJSON;
+    use crate::state::state_with_map::StateWithMap;
+    use crate::state::state_with_toml::StateWithTOML;
+    use crate::state::state_with_yaml::StateWithYAML;
+
+    #[test]
+    fn test_get_value_and_remove_value() {
+        let mut state: StateWithYAML = serde_yaml::from_str("alfa: 1\nbravo: [charlie]\n").expect("from_str");
+        assert_eq!(state.get_value("alfa"), Some(StateValue::Int(1)));
+        assert_eq!(state.remove_value("bravo"), Some(StateValue::from(json!(["charlie"]))));
+        assert_eq!(state.get_value("bravo"), None);
+    }
+
+    #[test]
+    fn test_insert_value_and_iter_values() {
+        let mut state: StateWithTOML = StateWithTOML::new();
+        state.insert_value(String::from("alfa"), StateValue::Bool(true));
+        state.insert_value(String::from("bravo"), StateValue::from(json!({"charlie": 1.5})));
+        let actual: Vec<(String, StateValue)> = state.iter_values().collect();
+        assert_eq!(actual, vec![
+            (String::from("alfa"), StateValue::Bool(true)),
+            (String::from("bravo"), StateValue::from(json!({"charlie": 1.5}))),
+        ]);
+    }
+
+    #[test]
+    fn test_insert_value_at_key_path() {
+        let mut state: StateWithYAML = serde_yaml::from_str("site:\n  title: alfa\n").expect("from_str");
+        state.insert_value_at_key_path("site.author.name", StateValue::from("Ada"));
+        state.insert_value_at_key_path("count", StateValue::Int(3));
+        assert_eq!(state.get_value("site"), Some(StateValue::from(json!({"title": "alfa", "author": {"name": "Ada"}}))));
+        assert_eq!(state.get_value("count"), Some(StateValue::Int(3)));
+    }
+
+    #[test]
+    fn test_deep_merge_x_yaml_into_json() {
+        let mut state: StateWithJSON = json!({"alfa": {"bravo": 1, "charlie": 2}, "delta": "echo"}).as_object().expect("object").to_owned();
+        let other: StateWithYAML = serde_yaml::from_str("alfa:\n  charlie: 3\nfoxtrot: true\n").expect("from_str");
+        state.deep_merge(&other);
+        assert_eq!(serde_json::Value::Object(state), json!({"alfa": {"bravo": 1, "charlie": 3}, "delta": "echo", "foxtrot": true}));
+    }
+
+    #[test]
+    fn test_deep_merge_x_toml_into_map() {
+        let mut state: StateWithMap = map!(String::from("alfa") => String::from("bravo"));
+        let other: StateWithTOML = toml::from_str("charlie = 1\n[delta]\necho = \"foxtrot\"\n").expect("from_str");
+        state.deep_merge(&other);
+        assert_eq!(state, map!(
+            String::from("alfa") => String::from("bravo"),
+            String::from("charlie") => String::from("1"),
+            String::from("delta") => String::from(r#"{"echo":"foxtrot"}"#)
+        ));
+    }
+
+}
@@ -0,0 +1,283 @@
+//! State value, i.e. one typed value that any state flavor can hold.
+//!
+//! Each flavor has its own value type, such as `serde_json::Value` or
+//! `toml::Value`, so this enum is the common ground that lets callers
+//! get, insert, and deep merge values without knowing the flavor.
+//!
+//! Conversions are lossless where the flavors agree, and otherwise:
+//!
+//! * TOML has no null, so a null becomes an empty TOML string.
+//!
+//! * TOML datetimes become strings, such as "2026-10-18".
+//!
+//! * YAML non-string keys become strings, and YAML tags are dropped.
+//!
+//! * The Map flavor holds only strings, so a list or map becomes JSON text.
+
+use serde::Serialize;
+use serde::ser::SerializeMap;
+use crate::types::{list::*, map::*};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum StateValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(List<StateValue>),
+    Map(Map<String, StateValue>),
+}
+
+impl StateValue {
+
+    /// Deep merge an overlay value into this value.
+    ///
+    /// When both values are maps, then merge each key recursively.
+    /// Otherwise the overlay value replaces this value.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// let mut base = StateValue::from(json!({"alfa": {"bravo": 1}}));
+    /// base.deep_merge(StateValue::from(json!({"alfa": {"charlie": 2}})));
+    /// //=> {"alfa": {"bravo": 1, "charlie": 2}}
+    /// ```
+    ///
+    pub fn deep_merge(&mut self, overlay: StateValue) {
+        match (self, overlay) {
+            (StateValue::Map(base), StateValue::Map(overlay)) => {
+                for (key, value) in overlay {
+                    match base.get_mut(&key) {
+                        Some(x) => x.deep_merge(value),
+                        None => { base.insert(key, value); },
+                    }
+                }
+            },
+            (base, overlay) => {
+                *base = overlay;
+            }
+        }
+    }
+
+    /// Convert to a plain string, such as for the Map flavor.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// assert_eq!(StateValue::Int(1).to_plain_string(), "1");
+    /// ```
+    ///
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            StateValue::Null => String::new(),
+            StateValue::Bool(x) => x.to_string(),
+            StateValue::Int(x) => x.to_string(),
+            StateValue::Float(x) => x.to_string(),
+            StateValue::String(x) => x.to_owned(),
+            StateValue::List(_) | StateValue::Map(_) => serde_json::to_string(self).unwrap_or_default(),
+        }
+    }
+
+}
+
+/// Deep merge of an overlay value over a base value, by reference.
+///
+/// This serializes the same as `StateValue::deep_merge`, yet without
+/// copying either value, such as for rendering every page with one site.
+///
+/// Example:
+///
+/// ```rust
+/// let base = StateValue::from(json!({"alfa": {"bravo": 1}}));
+/// let overlay = StateValue::from(json!({"alfa": {"charlie": 2}}));
+/// serde_json::to_value(StateValueDeepMerge { base: Some(&base), overlay: &overlay })
+/// //=> {"alfa": {"bravo": 1, "charlie": 2}}
+/// ```
+///
+#[derive(Debug)]
+pub struct StateValueDeepMerge<'a> {
+    pub base: Option<&'a StateValue>,
+    pub overlay: &'a StateValue,
+}
+
+impl Serialize for StateValueDeepMerge<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match (self.base, self.overlay) {
+            (Some(StateValue::Map(base)), StateValue::Map(overlay)) => {
+                let mut map = serializer.serialize_map(None)?;
+                for (key, value) in base.iter().filter(|(key, _)| !overlay.contains_key(*key)) {
+                    map.serialize_entry(key, value)?;
+                }
+                for (key, value) in overlay {
+                    map.serialize_entry(key, &StateValueDeepMerge { base: base.get(key), overlay: value })?;
+                }
+                map.end()
+            },
+            (_, overlay) => overlay.serialize(serializer),
+        }
+    }
+}
+
+impl From<String> for StateValue {
+    fn from(x: String) -> Self {
+        StateValue::String(x)
+    }
+}
+
+impl From<&str> for StateValue {
+    fn from(x: &str) -> Self {
+        StateValue::String(String::from(x))
+    }
+}
+
+impl From<serde_json::Value> for StateValue {
+    fn from(x: serde_json::Value) -> Self {
+        match x {
+            serde_json::Value::Null => StateValue::Null,
+            serde_json::Value::Bool(x) => StateValue::Bool(x),
+            serde_json::Value::Number(x) => match x.as_i64() {
+                Some(i) => StateValue::Int(i),
+                None => StateValue::Float(x.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(x) => StateValue::String(x),
+            serde_json::Value::Array(x) => StateValue::List(x.into_iter().map(StateValue::from).collect()),
+            serde_json::Value::Object(x) => StateValue::Map(x.into_iter().map(|(k, v)| (k, StateValue::from(v))).collect()),
+        }
+    }
+}
+
+impl From<StateValue> for serde_json::Value {
+    fn from(x: StateValue) -> Self {
+        match x {
+            StateValue::Null => serde_json::Value::Null,
+            StateValue::Bool(x) => serde_json::Value::Bool(x),
+            StateValue::Int(x) => serde_json::Value::from(x),
+            StateValue::Float(x) => serde_json::Value::from(x),
+            StateValue::String(x) => serde_json::Value::String(x),
+            StateValue::List(x) => serde_json::Value::Array(x.into_iter().map(serde_json::Value::from).collect()),
+            StateValue::Map(x) => serde_json::Value::Object(x.into_iter().map(|(k, v)| (k, serde_json::Value::from(v))).collect()),
+        }
+    }
+}
+
+impl From<toml::Value> for StateValue {
+    fn from(x: toml::Value) -> Self {
+        match x {
+            toml::Value::Boolean(x) => StateValue::Bool(x),
+            toml::Value::Integer(x) => StateValue::Int(x),
+            toml::Value::Float(x) => StateValue::Float(x),
+            toml::Value::String(x) => StateValue::String(x),
+            toml::Value::Datetime(x) => StateValue::String(x.to_string()),
+            toml::Value::Array(x) => StateValue::List(x.into_iter().map(StateValue::from).collect()),
+            toml::Value::Table(x) => StateValue::Map(x.into_iter().map(|(k, v)| (k, StateValue::from(v))).collect()),
+        }
+    }
+}
+
+impl From<StateValue> for toml::Value {
+    fn from(x: StateValue) -> Self {
+        match x {
+            StateValue::Null => toml::Value::String(String::new()),
+            StateValue::Bool(x) => toml::Value::Boolean(x),
+            StateValue::Int(x) => toml::Value::Integer(x),
+            StateValue::Float(x) => toml::Value::Float(x),
+            StateValue::String(x) => toml::Value::String(x),
+            StateValue::List(x) => toml::Value::Array(x.into_iter().map(toml::Value::from).collect()),
+            StateValue::Map(x) => toml::Value::Table(x.into_iter().map(|(k, v)| (k, toml::Value::from(v))).collect()),
+        }
+    }
+}
+
+impl From<serde_yaml::Value> for StateValue {
+    fn from(x: serde_yaml::Value) -> Self {
+        match x {
+            serde_yaml::Value::Null => StateValue::Null,
+            serde_yaml::Value::Bool(x) => StateValue::Bool(x),
+            serde_yaml::Value::Number(x) => match x.as_i64() {
+                Some(i) => StateValue::Int(i),
+                None => StateValue::Float(x.as_f64().unwrap_or_default()),
+            },
+            serde_yaml::Value::String(x) => StateValue::String(x),
+            serde_yaml::Value::Sequence(x) => StateValue::List(x.into_iter().map(StateValue::from).collect()),
+            serde_yaml::Value::Mapping(x) => StateValue::Map(x.into_iter().map(|(k, v)| (from_yaml_key_into_string(k), StateValue::from(v))).collect()),
+            serde_yaml::Value::Tagged(x) => StateValue::from(x.value),
+        }
+    }
+}
+
+impl From<StateValue> for serde_yaml::Value {
+    fn from(x: StateValue) -> Self {
+        match x {
+            StateValue::Null => serde_yaml::Value::Null,
+            StateValue::Bool(x) => serde_yaml::Value::Bool(x),
+            StateValue::Int(x) => serde_yaml::Value::from(x),
+            StateValue::Float(x) => serde_yaml::Value::from(x),
+            StateValue::String(x) => serde_yaml::Value::String(x),
+            StateValue::List(x) => serde_yaml::Value::Sequence(x.into_iter().map(serde_yaml::Value::from).collect()),
+            StateValue::Map(x) => serde_yaml::Value::Mapping(x.into_iter().map(|(k, v)| (serde_yaml::Value::String(k), serde_yaml::Value::from(v))).collect()),
+        }
+    }
+}
+
+/// Convert a YAML mapping key into a string, because state keys are strings.
+pub fn from_yaml_key_into_string(key: serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(x) => x,
+        x => StateValue::from(x).to_plain_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_json_and_back() {
+        let value = json!({"alfa": [1, 1.5, true, null, "bravo"], "charlie": {"delta": "echo"}});
+        let state_value = StateValue::from(value.clone());
+        assert_eq!(serde_json::Value::from(state_value), value);
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let value: toml::Value = toml::from_str("alfa = 1\nbravo = 2026-10-18\n[charlie]\ndelta = \"echo\"").expect("from_str");
+        let actual = StateValue::from(value);
+        assert_eq!(actual, StateValue::from(json!({"alfa": 1, "bravo": "2026-10-18", "charlie": {"delta": "echo"}})));
+    }
+
+    #[test]
+    fn test_from_yaml() {
+        let value: serde_yaml::Value = serde_yaml::from_str("alfa: 1\n2: two\ncharlie:\n  - delta\n").expect("from_str");
+        let actual = StateValue::from(value);
+        assert_eq!(actual, StateValue::from(json!({"alfa": 1, "2": "two", "charlie": ["delta"]})));
+    }
+
+    #[test]
+    fn test_deep_merge() {
+        let mut base = StateValue::from(json!({"alfa": {"bravo": 1, "charlie": 2}, "delta": [1]}));
+        base.deep_merge(StateValue::from(json!({"alfa": {"charlie": 3}, "delta": [2]})));
+        assert_eq!(base, StateValue::from(json!({"alfa": {"bravo": 1, "charlie": 3}, "delta": [2]})));
+    }
+
+    #[test]
+    fn test_state_value_deep_merge() {
+        let mut base = StateValue::from(json!({"alfa": {"bravo": 1, "charlie": 2}, "delta": [1], "echo": "foxtrot"}));
+        let overlay = StateValue::from(json!({"alfa": {"charlie": 3}, "delta": [2]}));
+        let actual = serde_json::to_value(StateValueDeepMerge { base: Some(&base), overlay: &overlay }).expect("to_value");
+        base.deep_merge(overlay.clone());
+        assert_eq!(actual, serde_json::Value::from(base));
+        let actual = serde_json::to_value(StateValueDeepMerge { base: None, overlay: &overlay }).expect("to_value");
+        assert_eq!(actual, serde_json::Value::from(overlay));
+    }
+
+    #[test]
+    fn test_to_plain_string() {
+        assert_eq!(StateValue::Null.to_plain_string(), "");
+        assert_eq!(StateValue::Int(1).to_plain_string(), "1");
+        assert_eq!(StateValue::from(json!(["alfa"])).to_plain_string(), "[\"alfa\"]");
+    }
+
+}
@@ -1,6 +1,7 @@
 use std::any::Any;
 use crate::state::state_trait::StateTrait;
 use crate::state::state_enum::StateEnum;
+use crate::state::state_value::StateValue;
 
 pub type StateWithJSON = ::serde_json::Map<String, ::serde_json::Value>;
 
@@ -22,4 +23,20 @@ impl StateTrait for StateWithJSON {
         self.insert(key, ::serde_json::Value::String(value));
     }
 
-}
\ No newline at end of file
+    fn get_value(&self, key: &str) -> Option<StateValue> {
+        self.get(key).map(|x| StateValue::from(x.to_owned()))
+    }
+
+    fn remove_value(&mut self, key: &str) -> Option<StateValue> {
+        self.remove(key).map(StateValue::from)
+    }
+
+    fn insert_value(&mut self, key: String, value: StateValue) {
+        self.insert(key, ::serde_json::Value::from(value));
+    }
+
+    fn iter_values(&self) -> Box<dyn Iterator<Item = (String, StateValue)> + '_> {
+        Box::new(self.iter().map(|(k, v)| (k.to_owned(), StateValue::from(v.to_owned()))))
+    }
+
+}
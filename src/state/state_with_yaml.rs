@@ -1,6 +1,7 @@
 use std::any::Any;
 use crate::state::state_trait::StateTrait;
 use crate::state::state_enum::StateEnum;
+use crate::state::state_value::{StateValue, from_yaml_key_into_string};
 
 pub type StateWithYAML = ::serde_yaml::Mapping;
 
@@ -22,4 +23,20 @@ impl StateTrait for StateWithYAML {
         self.insert(::serde_yaml::Value::String(String::from(key)), ::serde_yaml::Value::String(value));
     }
 
+    fn get_value(&self, key: &str) -> Option<StateValue> {
+        self.get(key).map(|x| StateValue::from(x.to_owned()))
+    }
+
+    fn remove_value(&mut self, key: &str) -> Option<StateValue> {
+        self.remove(key).map(StateValue::from)
+    }
+
+    fn insert_value(&mut self, key: String, value: StateValue) {
+        self.insert(::serde_yaml::Value::String(key), ::serde_yaml::Value::from(value));
+    }
+
+    fn iter_values(&self) -> Box<dyn Iterator<Item = (String, StateValue)> + '_> {
+        Box::new(self.iter().map(|(k, v)| (from_yaml_key_into_string(k.to_owned()), StateValue::from(v.to_owned()))))
+    }
+
 }
@@ -164,6 +164,29 @@ pub trait TemplaterTrait: std::fmt::Debug {
         state_enum: &crate::state::state_enum::StateEnum
     ) -> Result<HtmlString, impl std::error::Error>;
 
+    // Render a template name with the state and the variable `site`.
+    //
+    // The site is a separate layer over the state, such as a `StateValue`,
+    // rather than a copy in each page's state, so every page can share one
+    // site value.
+    //
+    // ```
+    // let templater: Templater = TemplaterWithHandlebars::new();
+    // templater.register_template_via_name_and_content("alfa", "<p>{{ bravo }} {{ site.title }}</p>");
+    // let state_enum = crate::state::state_enum::StateEnum::StateWithMap(map!("bravo" => "charlie"));
+    // let site = StateValue::from(json!({"title": "delta"}));
+    // let html = templater.render_template_with_state_enum_and_site("alfa", &state_enum, &site).expect("render_template_with_state_enum_and_site");
+    // assert_eq!(html, "<p>charlie delta</p>");
+    // ```
+    //
+    #[allow(dead_code)]
+    fn render_template_with_state_enum_and_site(
+        &self,
+        name: impl AsRef<str>,
+        state_enum: &crate::state::state_enum::StateEnum,
+        site: &impl serde::Serialize,
+    ) -> Result<HtmlString, impl std::error::Error>;
+
 }
 
 #[cfg(test)]
@@ -291,6 +314,20 @@ mod tests {
         let html = templater.render_template_with_state_enum("alfa", &state_enum).expect("render_template_with_state_enum");
         assert_eq!(html, "<p>charlie</p>");
     }
+
+    #[test]
+    fn test_render_template_with_state_enum_and_site() {
+        let mut templater: TemplaterWithHandlebars = TemplaterX::new();
+        templater.register_template_via_name_and_content("alfa", "<p>{{ bravo }} {{ site.title }}</p>").expect("register_template_via_name_and_content");
+        let map = map!(
+            String::from("bravo") => String::from("charlie"),
+            String::from("site") => String::from("echo")
+        );
+        let state_enum = crate::state::state_enum::StateEnum::StateWithMap(map);
+        let site = crate::state::state_value::StateValue::from(serde_json::json!({"title": "delta"}));
+        let html = templater.render_template_with_state_enum_and_site("alfa", &state_enum, &site).expect("render_template_with_state_enum_and_site");
+        assert_eq!(html, "<p>charlie delta</p>");
+    }
     
 }
 
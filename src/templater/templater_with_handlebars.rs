@@ -1,6 +1,7 @@
 //! Templater with Handlebars
 
 use handlebars::Handlebars;
+use serde::Serialize;
 use crate::app::args::Args;
 use crate::types::{html::*, set::*};
 use crate::state::state_enum::StateEnum;
@@ -109,6 +110,31 @@ impl<'templater> TemplaterTrait for TemplaterWithHandlebars<'templater> {
         )
     }
 
+    fn render_template_with_state_enum_and_site(
+        &self,
+        template_name: impl AsRef<str>,
+        state_enum: &StateEnum,
+        site: &impl Serialize,
+    ) -> Result<HtmlString, impl std::error::Error> {
+        trace!("render_template_with_state_enum_and_site");
+        self.handlebars.render(template_name.as_ref(), &StateEnumWithSite { state_enum, site })
+        .map_or_else(
+            |err| Err(Error::Render(err)),
+            Ok
+        )
+    }
+
+}
+
+/// Render data of a page state with the variable `site` as a separate layer,
+/// which borrows both, so rendering never copies the site into the state.
+///
+/// The site serializes after the state, so it wins over any state `site`.
+#[derive(Serialize)]
+struct StateEnumWithSite<'a, S: Serialize> {
+    #[serde(flatten)]
+    state_enum: &'a StateEnum,
+    site: &'a S,
 }
 
 
@@ -121,7 +147,8 @@ const TEMPLATE_CONTENT_DEFAULT: &str = r#"<!DOCTYPE html>
         <meta name="viewport" content="{{#if viewport }}{{ viewport }}{{else}}width=device-width, initial-scale=1.0{{/if}}">{{#if description}}
         <meta name="description" content="{{ description }}">{{/if}}{{#if keywords}}
         <meta name="keywords" content="{{ keywords }}">{{/if}}{{#if title}}
-        <title>{{ title }}</title>{{/if}}
+        <title>{{ title }}</title>{{/if}}{{#if translations}}{{#each translations}}
+        <link rel="alternate" hreflang="{{ @key }}" href="{{ this }}">{{/each}}{{/if}}
     </head>
     <body>
 {{#if content}}{{{ content }}}{{/if}}
@@ -59,3 +59,53 @@ pub fn remove_file_if_exists<P: AsRef<Path>>(path: P) -> std::io::Result<()>
         Ok(())
     }
 }
+
+/// A fresh output directory in the tmp directory, such as for one test.
+///
+/// Creating it removes any previous output, and dropping it removes the
+/// directory, unless the test is panicking, so a failing test's output
+/// stays for inspection.
+///
+/// Example:
+///
+/// ```rust
+/// let output = TmpOutputDir::new("cook_dir_test_site");
+/// cook_dir(&args, templater, None, &input, &output)?;
+/// assert_eq!(output.read("posts/alfa.html"), "Alfa");
+/// ```
+///
+#[allow(dead_code)]
+pub struct TmpOutputDir(PathBuf);
+
+#[allow(dead_code)]
+impl TmpOutputDir {
+
+    pub fn new(name: &str) -> Self {
+        let dir = TMP_DIR.join(name);
+        if dir.exists() { std::fs::remove_dir_all(&dir).expect("remove_dir_all"); }
+        std::fs::create_dir_all(&dir).expect("create_dir_all");
+        Self(dir)
+    }
+
+    /// Read a file in this directory into a String, such as "posts/alfa.html".
+    pub fn read<P: AsRef<Path>>(&self, path: P) -> String {
+        let path = self.0.join(path);
+        std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("read_to_string ➡ path: {:?}, err: {:?}", path, err))
+    }
+
+}
+
+impl std::ops::Deref for TmpOutputDir {
+    type Target = PathBuf;
+    fn deref(&self) -> &PathBuf {
+        &self.0
+    }
+}
+
+impl Drop for TmpOutputDir {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+}
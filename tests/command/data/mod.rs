@@ -0,0 +1,38 @@
+use crate::testing::*;
+use assertables::*;
+use once_cell::sync::Lazy;
+use std::path::PathBuf;
+
+pub static DIR: Lazy<PathBuf> =
+    Lazy::new(|| crate::testing::TESTS_DIR.join("command").join("data"));
+
+#[test]
+fn test() {
+    // Given
+    let data: PathBuf = DIR.join("data");
+    let template: PathBuf = DIR.join("template.html");
+    let input: PathBuf = DIR.join("example.md");
+    let output: PathBuf = DIR.join("example.html");
+    let expect: PathBuf = DIR.join("example.html=expect.html");
+    assert!(data.is_dir(), "data path: {:?}", data);
+    assert!(input.exists(), "input path: {:?}", input);
+    assert!(expect.exists(), "expect path: {:?}", expect);
+    assert_ok!(remove_file_if_exists(&output));
+    // When
+    let command_result = std::process::Command::new(&*COMMAND_OS)
+        .arg("--data")
+        .arg(data.as_os_str())
+        .arg("--template")
+        .arg(template.as_os_str())
+        .arg("--input")
+        .arg(input.as_os_str())
+        .arg("--output")
+        .arg(output.as_os_str())
+        .output();
+    // Then
+    assert_ok!(command_result);
+    assert!(output.exists(), "output.exists() path: {:?}", output);
+    assert_fs_read_to_string_eq!(&output, &expect);
+    // Done
+    assert_ok!(remove_file_if_exists(&output));
+}
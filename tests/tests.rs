@@ -1,8 +1,11 @@
 mod testing;
 
 mod command {
+    mod data;
+    mod env;
     mod input;
     mod output;
+    mod set;
     mod template;
 }
 mod markdown {